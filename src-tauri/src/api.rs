@@ -0,0 +1,103 @@
+use axum::extract::rejection::JsonRejection;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+use crate::normalize::NormalizationFix;
+use crate::validation::FieldError;
+
+// JSON error body shared by every route: { error, message, details? }
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiError {
+    #[serde(skip)]
+    pub status: StatusCode,
+    pub error: &'static str, // Machine-readable code
+    pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, error: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            error,
+            message: message.into(),
+            details: Vec::new(),
+        }
+    }
+
+    pub fn validation(details: Vec<FieldError>) -> Self {
+        Self {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            error: "validation_failed",
+            message: format!("{} invalid field(s)", details.len()),
+            details,
+        }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self)).into_response()
+    }
+}
+
+// Turn axum's plain-text body rejections into JSON errors
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        let error = match rejection {
+            JsonRejection::JsonDataError(_) => "invalid_payload",
+            JsonRejection::JsonSyntaxError(_) => "invalid_json",
+            JsonRejection::MissingJsonContentType(_) => "unsupported_media_type",
+            _ => "invalid_body",
+        };
+        Self::new(rejection.status(), error, rejection.body_text())
+    }
+}
+
+// 2xx body describing what was accepted
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "accepted", rename_all = "camelCase")]
+pub enum Ack {
+    #[serde(rename_all = "camelCase")]
+    Lyrics {
+        track: String,
+        lines: usize,
+        is_synced: bool,
+        source: String,
+        forwarded: bool, // False when another source currently owns the overlay
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        normalized: Vec<NormalizationFix>, // What the normalization pass changed, see normalize.rs
+    },
+    #[serde(rename_all = "camelCase")]
+    Progress {
+        position: u64,
+        is_playing: bool,
+        source: String,
+        forwarded: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Command {
+        id: u64,
+        ok: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Control {
+        action: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        locked: Option<bool>, // Lock state after lock/unlock/toggleLock
+    },
+    #[serde(rename_all = "camelCase")]
+    LyricsPatch {
+        track_id: String,
+        lines: usize,
+        source: String,
+        forwarded: bool,
+    },
+}
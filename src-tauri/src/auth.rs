@@ -0,0 +1,131 @@
+use axum::extract::Request;
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use rand::distributions::{Alphanumeric, DistString};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::api::ApiError;
+use crate::{status, AppState};
+
+const TOKEN_FILE_NAME: &str = "auth-token";
+const TOKEN_LENGTH: usize = 32;
+// Marks a non-browser client, browsers can't add it without a CORS preflight (which carries Origin)
+pub const CLIENT_HEADER: &str = "x-lyrics-plus-client";
+
+pub type SharedAuth = Arc<Mutex<AuthState>>;
+
+// Shared secret the ingest server expects when `required` is set
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthState {
+    pub token: String,
+    pub required: bool,
+}
+
+pub fn generate_token() -> String {
+    Alphanumeric.sample_string(&mut rand::thread_rng(), TOKEN_LENGTH)
+}
+
+// Read the token from the app config dir, generating and storing one on first run
+pub fn load_or_create_token<R: Runtime>(app: &AppHandle<R>) -> String {
+    if let Some(path) = token_path(app) {
+        if let Ok(token) = std::fs::read_to_string(&path) {
+            let token = token.trim();
+            if !token.is_empty() {
+                return token.to_string();
+            }
+        }
+    }
+
+    let token = generate_token();
+    if let Err(e) = save_token(app, &token) {
+        eprintln!("Failed to store auth token: {}", e);
+    }
+    token
+}
+
+pub fn save_token<R: Runtime>(app: &AppHandle<R>, token: &str) -> Result<(), String> {
+    let path = token_path(app).ok_or("App config dir unavailable")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, token).map_err(|e| e.to_string())?;
+
+    // Keep the secret readable by the current user only
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
+fn token_path<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    app.path().app_config_dir().ok().map(|dir| dir.join(TOKEN_FILE_NAME))
+}
+
+// Accepts `Authorization: Bearer <token>`, or `?token=<token>` for clients that can't set headers (EventSource, WebSocket)
+fn request_token(req: &Request) -> Option<String> {
+    if let Some(value) = req.headers().get(header::AUTHORIZATION).and_then(|v| v.to_str().ok()) {
+        if let Some(token) = value.strip_prefix("Bearer ") {
+            return Some(token.trim().to_string());
+        }
+    }
+
+    req.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+            .map(|token| token.to_string())
+    })
+}
+
+// Compare without bailing out on the first mismatching byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn unauthorized(error: &'static str, message: &'static str) -> Response {
+    (
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        ApiError::new(StatusCode::UNAUTHORIZED, error, message),
+    )
+        .into_response()
+}
+
+// Middleware applied to every route of the ingest server
+pub async fn require_token<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let (required, expected) = match state.auth.lock() {
+        Ok(auth) => (auth.required, auth.token.clone()),
+        Err(e) => return ApiError::internal(e.to_string()).into_response(),
+    };
+
+    // Remote control always needs the token, even when ingest is open. So do command routes
+    // reached without Origin and the client header: browsers leave Origin out of no-cors GETs,
+    // and any web page could otherwise drain the command queue
+    let path = req.uri().path();
+    let headers = req.headers();
+    let anonymous_command = status::is_command_path(path)
+        && !headers.contains_key(header::ORIGIN)
+        && !headers.contains_key(CLIENT_HEADER);
+    let required = (required && !status::is_public_path(path)) || status::is_control_path(path) || anonymous_command;
+    if required {
+        match request_token(&req) {
+            None => return unauthorized("missing_token", "Missing bearer token"),
+            Some(token) if !constant_time_eq(token.as_bytes(), expected.as_bytes()) => {
+                return unauthorized("invalid_token", "Invalid bearer token");
+            }
+            Some(_) => {}
+        }
+    }
+
+    next.run(req).await
+}
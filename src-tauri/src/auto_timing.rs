@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::LyricsData;
+
+// Share of the track before the first line, and cap, for the weighted estimate
+const INTRO_RATIO: f64 = 0.1;
+const MAX_INTRO_MS: f64 = 20_000.0;
+// Same for the part after the last line
+const OUTRO_RATIO: f64 = 0.08;
+const MAX_OUTRO_MS: f64 = 15_000.0;
+// Weight of an empty line (a break between verses)
+const EMPTY_LINE_WEIGHT: f64 = 2.0;
+
+pub type SharedUnsyncedTiming = Arc<Mutex<UnsyncedTiming>>;
+
+// How line timings are made up for lyrics sent with `isSynced: false`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UnsyncedTiming {
+    Off, // Leave them unsynced, the overlay doesn't show them
+    #[default]
+    Weighted, // By syllables per line, with intro/outro margins
+    Proportional, // Same time for every line over the whole track
+}
+
+pub fn current<R: Runtime>(app: &AppHandle<R>) -> UnsyncedTiming {
+    app.try_state::<SharedUnsyncedTiming>()
+        .and_then(|timing| timing.lock().ok().map(|t| *t))
+        .unwrap_or_default()
+}
+
+// Fill in start/end times of unsynced lyrics and record how they were estimated
+pub fn estimate(lyrics_data: &mut LyricsData, timing: UnsyncedTiming) {
+    if lyrics_data.is_synced {
+        return;
+    }
+    let duration = lyrics_data.track.duration as f64;
+    if timing == UnsyncedTiming::Off || duration <= 0.0 || lyrics_data.lyrics.is_empty() {
+        lyrics_data.estimated_timing = None;
+        return;
+    }
+
+    let (start, end, weights): (f64, f64, Vec<f64>) = match timing {
+        UnsyncedTiming::Proportional => (0.0, duration, vec![1.0; lyrics_data.lyrics.len()]),
+        _ => (
+            (duration * INTRO_RATIO).min(MAX_INTRO_MS),
+            duration - (duration * OUTRO_RATIO).min(MAX_OUTRO_MS),
+            lyrics_data.lyrics.iter().map(|line| line_weight(&line.text)).collect(),
+        ),
+    };
+
+    let total: f64 = weights.iter().sum();
+    let mut at = start;
+    for (line, weight) in lyrics_data.lyrics.iter_mut().zip(weights) {
+        let length = (end - start) * weight / total;
+        line.start_time = at.round() as i64;
+        line.end_time = Some((at + length).round() as i64);
+        at += length;
+    }
+    lyrics_data.estimated_timing = Some(timing);
+}
+
+fn line_weight(text: &str) -> f64 {
+    let syllables = text.split_whitespace().map(word_syllables).sum::<usize>();
+    if syllables == 0 {
+        EMPTY_LINE_WEIGHT
+    } else {
+        syllables as f64
+    }
+}
+
+// CJK characters and Hangul blocks are one syllable each, Latin words count vowel groups
+fn word_syllables(word: &str) -> usize {
+    let mut syllables = 0;
+    let mut in_vowel_group = false;
+    let mut has_latin = false;
+    for c in word.chars() {
+        if c.is_ascii_alphabetic() || ('\u{00C0}'..='\u{024F}').contains(&c) {
+            has_latin = true;
+            let is_vowel = "aeiouyAEIOUY".contains(c) || !c.is_ascii();
+            if is_vowel && !in_vowel_group {
+                syllables += 1;
+            }
+            in_vowel_group = is_vowel;
+        } else {
+            in_vowel_group = false;
+            if c.is_alphanumeric() {
+                syllables += 1;
+            }
+        }
+    }
+    // Words like "rhythm" or "sh" still take time to sing
+    if has_latin && syllables == 0 {
+        1
+    } else {
+        syllables
+    }
+}
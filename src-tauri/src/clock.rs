@@ -0,0 +1,178 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Runtime};
+use tokio::sync::Notify;
+
+use crate::events::{self, BusEvent};
+use crate::{LyricsData, ProgressData};
+
+pub type SharedPlaybackClock = Arc<PlaybackClock>;
+
+// Payload of the `line-change` event, indexes into the current `LyricsData.lyrics`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineChangeEvent {
+    pub previous: Option<usize>, // Line that was active before this change
+    pub current: Option<usize>, // None before the first line or without synced lyrics
+    pub next: Option<usize>,
+    pub position: u64,
+}
+
+// Returned by the get_playback_clock command
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClockSnapshot {
+    pub position: u64,
+    pub is_playing: bool,
+    pub current: Option<usize>,
+    pub next: Option<usize>,
+}
+
+#[derive(Debug)]
+struct ClockState {
+    anchor_position: u64, // Position reported by the last progress update
+    anchor_at: Instant,
+    is_playing: bool,
+    line_starts: Vec<i64>, // Start times of the synced lines, empty for unsynced lyrics
+    current: Option<usize>,
+}
+
+impl Default for ClockState {
+    fn default() -> Self {
+        Self {
+            anchor_position: 0,
+            anchor_at: Instant::now(),
+            is_playing: false,
+            line_starts: Vec::new(),
+            current: None,
+        }
+    }
+}
+
+impl ClockState {
+    // Extrapolated from the last progress update
+    fn position(&self) -> u64 {
+        if self.is_playing {
+            self.anchor_position + self.anchor_at.elapsed().as_millis() as u64
+        } else {
+            self.anchor_position
+        }
+    }
+
+    // Last line that started at or before `position`, same rule the overlay used
+    fn line_at(&self, position: u64) -> Option<usize> {
+        let started = self.line_starts.partition_point(|&start| start <= position as i64);
+        started.checked_sub(1)
+    }
+
+    fn next_line(&self, current: Option<usize>) -> Option<usize> {
+        let next = current.map_or(0, |i| i + 1);
+        (next < self.line_starts.len()).then_some(next)
+    }
+}
+
+// Tracks the playback position between progress updates and wakes up at line boundaries
+#[derive(Default)]
+pub struct PlaybackClock {
+    state: Mutex<ClockState>,
+    changed: Notify,
+}
+
+impl PlaybackClock {
+    pub fn set_lyrics(&self, lyrics_data: &LyricsData) {
+        if let Ok(mut s) = self.state.lock() {
+            s.line_starts = if lyrics_data.has_timing() {
+                lyrics_data.lyrics.iter().map(|line| line.start_time).collect()
+            } else {
+                Vec::new()
+            };
+        }
+        // notify_one keeps the permit if the driver is busy emitting
+        self.changed.notify_one();
+    }
+
+    pub fn set_progress(&self, progress_data: &ProgressData) {
+        if let Ok(mut s) = self.state.lock() {
+            s.anchor_position = progress_data.position;
+            s.anchor_at = Instant::now();
+            s.is_playing = progress_data.is_playing;
+        }
+        self.changed.notify_one();
+    }
+
+    // The active source has no lyrics / progress yet
+    pub fn clear(&self, lyrics: bool, progress: bool) {
+        if let Ok(mut s) = self.state.lock() {
+            if lyrics {
+                s.line_starts.clear();
+            }
+            if progress {
+                s.anchor_position = 0;
+                s.is_playing = false;
+            }
+        }
+        self.changed.notify_one();
+    }
+
+    pub fn snapshot(&self) -> Option<ClockSnapshot> {
+        let s = self.state.lock().ok()?;
+        Some(ClockSnapshot {
+            position: s.position(),
+            is_playing: s.is_playing,
+            current: s.current,
+            next: s.next_line(s.current),
+        })
+    }
+
+    // Resolve the active line, returns the change (if any) and how long until the next boundary
+    fn tick(&self) -> (Option<LineChangeEvent>, Option<Duration>) {
+        let mut s = match self.state.lock() {
+            Ok(s) => s,
+            Err(_) => return (None, None),
+        };
+
+        let position = s.position();
+        let current = s.line_at(position);
+        let next = s.next_line(current);
+
+        let change = (current != s.current).then_some(LineChangeEvent {
+            previous: s.current,
+            current,
+            next,
+            position,
+        });
+        s.current = current;
+
+        // Paused playback never reaches the next line on its own
+        let wait = match next {
+            Some(next) if s.is_playing => {
+                let boundary = s.line_starts[next].max(0) as u64;
+                Some(Duration::from_millis(boundary.saturating_sub(position).max(1)))
+            }
+            _ => None,
+        };
+        (change, wait)
+    }
+}
+
+// Emit `line-change` exactly when the active line changes, sleeping until the next boundary
+pub async fn drive<R: Runtime>(app: AppHandle<R>, clock: SharedPlaybackClock) {
+    loop {
+        let (change, wait) = clock.tick();
+        if let Some(event) = change {
+            let _ = app.emit("line-change", event);
+            events::publish(&app, BusEvent::LineChange(event));
+        }
+
+        match wait {
+            Some(wait) => {
+                tokio::select! {
+                    _ = tokio::time::sleep(wait) => {}
+                    _ = clock.changed.notified() => {}
+                }
+            }
+            None => clock.changed.notified().await,
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use axum::extract::{Path, Query};
+use axum::extract::rejection::JsonRejection;
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::Notify;
+
+use crate::api::{Ack, ApiError};
+use crate::latency::SharedLatency;
+use crate::ws::{ClientPush, ServerMessage};
+use crate::AppState;
+
+// Overlay -> player commands, delivered over WebSocket/IPC push or GET /commands long-polling
+
+// Commands that weren't acknowledged by then are dropped (a seek this late would be wrong)
+const COMMAND_TTL: Duration = Duration::from_secs(10);
+// Long-poll wait when the client doesn't ask for one, and the most we allow
+const DEFAULT_POLL_TIMEOUT_MS: u64 = 25_000;
+const MAX_POLL_TIMEOUT_MS: u64 = 60_000;
+
+pub type SharedCommandQueue = Arc<CommandQueue>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PlayerCommand {
+    #[serde(rename_all = "camelCase")]
+    Seek { position_ms: u64 },
+    PlayPause,
+    Next,
+    Previous,
+    ResendLyrics,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedCommand {
+    pub id: u64,
+    #[serde(flatten)]
+    pub command: PlayerCommand,
+    pub expires_in_ms: u64,
+}
+
+// Sent by the extension once a command was executed (or failed)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CommandResult {
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+// Payload of the `command-ack` and `command-expired` events
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandEvent {
+    pub id: u64,
+    pub command: PlayerCommand,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ok: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+struct Entry {
+    id: u64,
+    command: PlayerCommand,
+    delivered: bool,
+}
+
+#[derive(Default)]
+struct Pending {
+    last_id: u64,
+    entries: Vec<Entry>,
+}
+
+#[derive(Default)]
+pub struct CommandQueue {
+    pending: Mutex<Pending>,
+    available: Notify,
+}
+
+impl CommandQueue {
+    fn push(&self, command: PlayerCommand) -> QueuedCommand {
+        let id = {
+            let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            pending.last_id += 1;
+            let id = pending.last_id;
+            pending.entries.push(Entry { id, command: command.clone(), delivered: false });
+            id
+        };
+
+        self.available.notify_waiters();
+        QueuedCommand { id, command, expires_in_ms: COMMAND_TTL.as_millis() as u64 }
+    }
+
+    fn mark_delivered(&self, id: u64) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = pending.entries.iter_mut().find(|e| e.id == id) {
+            entry.delivered = true;
+        }
+    }
+
+    // Undelivered commands, oldest first; they count as delivered afterwards
+    fn take_undelivered(&self) -> Vec<QueuedCommand> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending
+            .entries
+            .iter_mut()
+            .filter(|e| !e.delivered)
+            .map(|e| {
+                e.delivered = true;
+                QueuedCommand {
+                    id: e.id,
+                    command: e.command.clone(),
+                    expires_in_ms: COMMAND_TTL.as_millis() as u64,
+                }
+            })
+            .collect()
+    }
+
+    fn remove(&self, id: u64) -> Option<PlayerCommand> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let index = pending.entries.iter().position(|e| e.id == id)?;
+        Some(pending.entries.remove(index).command)
+    }
+
+    // Wait until something is queued or the timeout passes
+    async fn poll(&self, timeout: Duration) -> Vec<QueuedCommand> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            // Register before checking so a push in between isn't missed
+            let notified = self.available.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            let commands = self.take_undelivered();
+            if !commands.is_empty() {
+                return commands;
+            }
+            tokio::select! {
+                _ = notified => {}
+                _ = tokio::time::sleep_until(deadline) => return Vec::new(),
+            }
+        }
+    }
+}
+
+// Queue a command, push it to connected clients and schedule its expiry
+pub fn enqueue<R: Runtime>(app: &AppHandle<R>, mut command: PlayerCommand) -> QueuedCommand {
+    // The overlay seeks to line times, which include the delay and offsets added to positions
+    if let PlayerCommand::Seek { position_ms } = &mut command {
+        if let Some(latency) = app.try_state::<SharedLatency>() {
+            if let Ok(l) = latency.lock() {
+                *position_ms = l.to_player_position(*position_ms);
+            }
+        }
+    }
+    let queue = app.state::<SharedCommandQueue>().inner().clone();
+    let queued = queue.push(command);
+
+    // Pushed to at least one socket -> long-pollers must not get it again
+    let pushed = app
+        .try_state::<ClientPush>()
+        .and_then(|push| push.send(ServerMessage::Command(queued.clone())).ok())
+        .unwrap_or(0);
+    if pushed > 0 {
+        queue.mark_delivered(queued.id);
+    }
+
+    let app = app.clone();
+    let id = queued.id;
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(COMMAND_TTL).await;
+        if let Some(command) = queue.remove(id) {
+            println!("Player command {} expired", id);
+            let _ = app.emit("command-expired", CommandEvent { id, command, ok: None, error: None });
+        }
+    });
+
+    queued
+}
+
+// Shared by POST /commands/:id/ack and the WebSocket/IPC `commandAck` frame
+pub fn acknowledge<R: Runtime>(app: &AppHandle<R>, id: u64, result: CommandResult) -> Result<Ack, ApiError> {
+    let command = app.state::<SharedCommandQueue>().remove(id).ok_or_else(|| {
+        ApiError::new(StatusCode::NOT_FOUND, "unknown_command", format!("Command {} is not pending (expired or already acknowledged)", id))
+    })?;
+
+    let ok = result.ok;
+    let _ = app.emit("command-ack", CommandEvent { id, command, ok: Some(ok), error: result.error });
+    Ok(Ack::Command { id, ok })
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollQuery {
+    timeout_ms: Option<u64>,
+}
+
+// GET /commands: long-poll for pending commands, `[]` when the timeout passes
+pub async fn handle_poll<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+    Query(query): Query<PollQuery>,
+) -> Json<Vec<QueuedCommand>> {
+    let timeout = query.timeout_ms.unwrap_or(DEFAULT_POLL_TIMEOUT_MS).min(MAX_POLL_TIMEOUT_MS);
+    let queue = state.app_handle.state::<SharedCommandQueue>().inner().clone();
+    Json(queue.poll(Duration::from_millis(timeout)).await)
+}
+
+// POST /commands/:id/ack
+pub async fn handle_ack<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+    Path(id): Path<u64>,
+    payload: Result<Json<CommandResult>, JsonRejection>,
+) -> Result<Json<Ack>, ApiError> {
+    let Json(result) = payload?;
+    acknowledge(&state.app_handle, id, result).map(Json)
+}
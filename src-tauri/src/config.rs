@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::auto_timing::UnsyncedTiming;
+use crate::sources::ArbitrationPolicy;
+
+const CONFIG_FILE_NAME: &str = "backend.json";
+const DISCOVERY_FILE_NAME: &str = "server.json";
+
+// Environment variable overrides
+const ENV_HOST: &str = "LYRICS_OVERLAY_HOST";
+const ENV_PORT: &str = "LYRICS_OVERLAY_PORT";
+
+// Backend configuration, stored as JSON in the app config dir
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BackendConfig {
+    pub host: String,
+    pub port: u16,
+    pub port_fallback_attempts: u16, // How many following ports to try when `port` is taken
+    pub require_token: bool, // Reject requests without the shared secret from the auth-token file
+    pub allowed_origins: Vec<String>, // Browser origins allowed to call the server (CORS allowlist)
+    pub source_policy: ArbitrationPolicy, // How to pick between several players posting at once
+    pub position_offset_ms: i64, // Manual lyrics offset added to every progress position
+    pub progress_max_rate: f64, // Max progress-update events per second sent to the overlay, 0 = unlimited
+    pub transport: Transport,
+    pub ipc_path: Option<String>, // Socket / pipe path, defaults to ipc::default_path
+    pub translation_languages: Vec<String>, // Translations shown in the overlay, in order, empty = all
+    pub interlude_threshold_ms: u64, // Shortest gap between lines marked as an interlude, 0 = never
+    pub unsynced_timing: UnsyncedTiming, // How lyrics without timestamps are spread over the track
+    pub full_width_punctuation: bool, // Convert full-width punctuation (！？，) in lyrics to ASCII
+}
+
+// Which listeners the server opens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Transport {
+    #[default]
+    Tcp,
+    Ipc, // Unix domain socket, or a named pipe on Windows
+    Both,
+}
+
+impl Transport {
+    pub fn uses_tcp(self) -> bool {
+        matches!(self, Transport::Tcp | Transport::Both)
+    }
+
+    pub fn uses_ipc(self) -> bool {
+        matches!(self, Transport::Ipc | Transport::Both)
+    }
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 15000,
+            port_fallback_attempts: 10,
+            require_token: false,
+            allowed_origins: vec![
+                "https://xpui.app.spotify.com".to_string(),
+                "http://localhost".to_string(),
+                "http://127.0.0.1".to_string(),
+            ],
+            source_policy: ArbitrationPolicy::default(),
+            position_offset_ms: 0,
+            progress_max_rate: 10.0,
+            transport: Transport::default(),
+            ipc_path: None,
+            translation_languages: Vec::new(),
+            interlude_threshold_ms: 8000,
+            unsynced_timing: UnsyncedTiming::default(),
+            full_width_punctuation: false,
+        }
+    }
+}
+
+impl BackendConfig {
+    // Load config from disk, then apply env and CLI overrides
+    // Precedence: CLI > env > config file > defaults
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let mut config = Self::read(app);
+        config.apply_env();
+        config.apply_args(std::env::args().skip(1));
+        config
+    }
+
+    // Config file contents only (creating it with defaults if missing), used when saving changes
+    pub fn read<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = match config_path(app) {
+            Some(path) => path,
+            None => return Self::default(),
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                eprintln!("Invalid backend config at {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => {
+                let config = Self::default();
+                if let Err(e) = config.save(app) {
+                    eprintln!("Failed to write default backend config: {}", e);
+                }
+                config
+            }
+        }
+    }
+
+    pub fn save<R: Runtime>(&self, app: &AppHandle<R>) -> Result<(), String> {
+        let path = config_path(app).ok_or("App config dir unavailable")?;
+        write_json(&path, self)
+    }
+
+    fn apply_env(&mut self) {
+        if let Ok(host) = std::env::var(ENV_HOST) {
+            if !host.is_empty() {
+                self.host = host;
+            }
+        }
+        if let Ok(port) = std::env::var(ENV_PORT) {
+            match port.parse() {
+                Ok(port) => self.port = port,
+                Err(_) => eprintln!("Ignoring invalid {}: {}", ENV_PORT, port),
+            }
+        }
+    }
+
+    // Supports `--host <host>`, `--port <port>` and the `--flag=value` forms
+    fn apply_args<I: Iterator<Item = String>>(&mut self, mut args: I) {
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+
+            match flag.as_str() {
+                "--host" => {
+                    if let Some(host) = inline_value.or_else(|| args.next()) {
+                        self.host = host;
+                    }
+                }
+                "--port" => {
+                    if let Some(port) = inline_value.or_else(|| args.next()) {
+                        match port.parse() {
+                            Ok(port) => self.port = port,
+                            Err(_) => eprintln!("Ignoring invalid --port: {}", port),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Ports to try in order: preferred port first, then the following ones
+    pub fn candidate_ports(&self) -> impl Iterator<Item = u16> {
+        let first = self.port;
+        let last = first.saturating_add(self.port_fallback_attempts);
+        first..=last
+    }
+}
+
+// Info about the running server, written to the discovery file
+// TCP fields are absent when only the IPC transport is enabled and vice versa
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipc_path: Option<String>,
+    pub pid: u32,
+}
+
+impl ServerInfo {
+    pub fn new(addr: Option<SocketAddr>, ipc_path: Option<String>) -> Self {
+        Self {
+            host: addr.map(|addr| addr.ip().to_string()),
+            port: addr.map(|addr| addr.port()),
+            url: addr.map(|addr| format!("http://{}", addr)),
+            ipc_path,
+            pid: std::process::id(),
+        }
+    }
+}
+
+// Write the discovery file so the Spicetify extension / scripts can find the actual port
+pub fn write_discovery_file<R: Runtime>(app: &AppHandle<R>, info: &ServerInfo) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let path = dir.join(DISCOVERY_FILE_NAME);
+    write_json(&path, info)?;
+    Ok(path)
+}
+
+fn config_path<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    app.path().app_config_dir().ok().map(|dir| dir.join(CONFIG_FILE_NAME))
+}
+
+pub(crate) fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let contents = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    std::fs::write(path, contents).map_err(|e| e.to_string())
+}
@@ -0,0 +1,180 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::{LyricsData, ProgressData};
+
+// A position this far from where playback should be counts as a seek
+pub const SEEK_THRESHOLD_MS: i64 = 1500;
+// Playing without the position moving for this long counts as a stall
+const STALL_AFTER: Duration = Duration::from_secs(2);
+// Movement below this is jitter, not progress
+const STALL_TOLERANCE_MS: i64 = 250;
+
+pub type SharedPlaybackDetector = Arc<Mutex<PlaybackDetector>>;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeekEvent {
+    pub from: u64, // Position of the previous update
+    pub to: u64,
+    pub expected: u64, // Where normal playback would have been
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StallEvent {
+    pub stalled: bool, // False once the position moves again
+    pub position_before: u64, // Position when it stopped advancing
+    pub position_after: u64,
+    pub stalled_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackSummary {
+    pub id: String,
+    pub title: String,
+    pub artist: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackChangeEvent {
+    pub from: Option<TrackSummary>,
+    pub to: TrackSummary,
+}
+
+// Events derived from consecutive updates, emitted as `seek` / `stall` / `track-change`
+#[derive(Debug, Clone)]
+pub enum PlaybackEvent {
+    Seek(SeekEvent),
+    Stall(StallEvent),
+    TrackChange(TrackChangeEvent),
+}
+
+impl PlaybackEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlaybackEvent::Seek(_) => "seek",
+            PlaybackEvent::Stall(_) => "stall",
+            PlaybackEvent::TrackChange(_) => "track-change",
+        }
+    }
+
+    pub fn emit<R: Runtime>(&self, app: &AppHandle<R>) {
+        let _ = match self {
+            PlaybackEvent::Seek(payload) => app.emit(self.name(), payload),
+            PlaybackEvent::Stall(payload) => app.emit(self.name(), payload),
+            PlaybackEvent::TrackChange(payload) => app.emit(self.name(), payload),
+        };
+    }
+}
+
+#[derive(Debug)]
+struct LastProgress {
+    position: u64,
+    is_playing: bool,
+    duration: Option<u64>,
+    at: Instant,
+}
+
+#[derive(Debug, Default)]
+pub struct PlaybackDetector {
+    last: Option<LastProgress>,
+    advanced: Option<(u64, Instant)>, // Position and time it last moved while playing
+    stalled: bool,
+    track: Option<TrackSummary>,
+}
+
+// Where playback should be `elapsed` after `position`
+pub fn expected_position(position: u64, is_playing: bool, elapsed: Duration) -> u64 {
+    if is_playing {
+        position + elapsed.as_millis() as u64
+    } else {
+        position
+    }
+}
+
+impl PlaybackDetector {
+    pub fn on_lyrics(&mut self, lyrics_data: &LyricsData) -> Vec<PlaybackEvent> {
+        let to = TrackSummary {
+            id: lyrics_data.track_id().to_string(),
+            title: lyrics_data.track.title.clone(),
+            artist: lyrics_data.track.artist.clone(),
+        };
+        if self.track.as_ref().is_some_and(|track| track.id == to.id) {
+            return Vec::new();
+        }
+
+        // The position restarting on the new track is not a seek
+        self.last = None;
+        self.advanced = None;
+        self.stalled = false;
+        let from = self.track.replace(to.clone());
+        vec![PlaybackEvent::TrackChange(TrackChangeEvent { from, to })]
+    }
+
+    pub fn on_progress(&mut self, progress_data: &ProgressData) -> Vec<PlaybackEvent> {
+        let now = Instant::now();
+        let position = progress_data.position;
+        let mut detected = Vec::new();
+
+        if let Some(last) = &self.last {
+            // A different duration means the player moved on before the lyrics arrived
+            let same_track = last.duration == progress_data.duration || progress_data.duration.is_none();
+            // Not moving at all while playing is a stall, handled below
+            let moved = (position as i64 - last.position as i64).abs() > STALL_TOLERANCE_MS;
+            let expected = expected_position(last.position, last.is_playing, now - last.at);
+            if same_track && moved && (position as i64 - expected as i64).abs() > SEEK_THRESHOLD_MS {
+                detected.push(PlaybackEvent::Seek(SeekEvent { from: last.position, to: position, expected }));
+                self.advanced = None;
+            }
+        }
+
+        if progress_data.is_playing {
+            match self.advanced {
+                Some((since_position, since)) if (position as i64 - since_position as i64).abs() <= STALL_TOLERANCE_MS => {
+                    if !self.stalled && now - since >= STALL_AFTER {
+                        self.stalled = true;
+                        detected.push(self.stall_event(since_position, position, now - since));
+                    }
+                }
+                _ => {
+                    if self.stalled {
+                        let (since_position, since) = self.advanced.unwrap_or((position, now));
+                        self.stalled = false;
+                        detected.push(self.stall_event(since_position, position, now - since));
+                    }
+                    self.advanced = Some((position, now));
+                }
+            }
+        } else {
+            // Paused is not stalled
+            if self.stalled {
+                let (since_position, since) = self.advanced.unwrap_or((position, now));
+                self.stalled = false;
+                detected.push(self.stall_event(since_position, position, now - since));
+            }
+            self.advanced = None;
+        }
+
+        self.last = Some(LastProgress {
+            position,
+            is_playing: progress_data.is_playing,
+            duration: progress_data.duration,
+            at: now,
+        });
+        detected
+    }
+
+    fn stall_event(&self, position_before: u64, position_after: u64, stalled_for: Duration) -> PlaybackEvent {
+        PlaybackEvent::Stall(StallEvent {
+            stalled: self.stalled,
+            position_before,
+            position_after,
+            stalled_ms: stalled_for.as_millis() as u64,
+        })
+    }
+}
@@ -0,0 +1,106 @@
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::stream::{self, Stream, StreamExt};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::sync::broadcast;
+
+use crate::clock::LineChangeEvent;
+use crate::detect::PlaybackEvent;
+use crate::now_playing::ClearedEvent;
+use crate::patch::LyricsPatchEvent;
+use crate::{AppLockState, AppState, LyricsEvent, ProgressEvent};
+
+// Capacity of the event bus; slow SSE consumers skip what they missed
+const EVENT_BUS_CAPACITY: usize = 64;
+
+pub type EventBus = broadcast::Sender<BusEvent>;
+
+pub fn event_bus() -> EventBus {
+    broadcast::channel(EVENT_BUS_CAPACITY).0
+}
+
+// Events published to external consumers, named like the frontend events
+#[derive(Debug, Clone)]
+pub enum BusEvent {
+    Lyrics(LyricsEvent),
+    Progress(ProgressEvent),
+    LyricsPatch(LyricsPatchEvent),
+    LineChange(LineChangeEvent),
+    Playback(PlaybackEvent),
+    LockState(bool),
+    SourceChange(String),
+    Cleared(ClearedEvent),
+}
+
+impl BusEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BusEvent::Lyrics(_) => "lyrics-update",
+            BusEvent::Progress(_) => "progress-update",
+            BusEvent::LyricsPatch(_) => "lyrics-patch",
+            BusEvent::LineChange(_) => "line-change",
+            BusEvent::Playback(event) => event.name(),
+            BusEvent::LockState(_) => "lock-state-update",
+            BusEvent::SourceChange(_) => "source-change",
+            BusEvent::Cleared(_) => "now-playing-cleared",
+        }
+    }
+
+    fn to_sse(&self) -> Result<Event, axum::Error> {
+        let event = Event::default().event(self.name());
+        match self {
+            BusEvent::Lyrics(payload) => event.json_data(payload),
+            BusEvent::Progress(payload) => event.json_data(payload),
+            BusEvent::LyricsPatch(payload) => event.json_data(payload),
+            BusEvent::LineChange(payload) => event.json_data(payload),
+            BusEvent::Playback(PlaybackEvent::Seek(payload)) => event.json_data(payload),
+            BusEvent::Playback(PlaybackEvent::Stall(payload)) => event.json_data(payload),
+            BusEvent::Playback(PlaybackEvent::TrackChange(payload)) => event.json_data(payload),
+            BusEvent::LockState(payload) => event.json_data(payload),
+            BusEvent::SourceChange(payload) => event.json_data(payload),
+            BusEvent::Cleared(payload) => event.json_data(payload),
+        }
+    }
+}
+
+// Publish through the managed bus, usable anywhere an AppHandle is available
+pub fn publish<R: Runtime>(app: &AppHandle<R>, event: BusEvent) {
+    if let Some(bus) = app.try_state::<EventBus>() {
+        // Err only means nobody is subscribed
+        let _ = bus.send(event);
+    }
+}
+
+// GET /events: Server-Sent Events stream, starting with the current state
+pub async fn handle_events<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    // Subscribe before taking the snapshot so nothing in between is lost
+    let receiver = state.events.subscribe();
+
+    let mut initial = Vec::new();
+    if let Ok(now_playing) = state.now_playing.lock() {
+        if let Some(lyrics_data) = now_playing.lyrics.clone() {
+            initial.push(BusEvent::Lyrics(LyricsEvent { lyrics_data }));
+        }
+        if let Some(progress_data) = now_playing.progress.clone() {
+            initial.push(BusEvent::Progress(ProgressEvent { progress_data }));
+        }
+    }
+    if let Ok(lock_state) = state.app_handle.state::<Arc<Mutex<AppLockState>>>().lock() {
+        initial.push(BusEvent::LockState(lock_state.is_locked));
+    }
+
+    let live = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((event, receiver)),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    let events = stream::iter(initial).chain(live).map(|event| event.to_sse());
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
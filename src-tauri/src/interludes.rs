@@ -0,0 +1,51 @@
+use crate::{LineKind, LyricLine, LyricsData};
+
+// How long a line without `endTime` is assumed to be sung before an interlude can start
+const ASSUMED_LINE_MS: i64 = 5000;
+
+// Replace any markers already present (e.g. a client echoing GET /state) with fresh ones
+// for every gap of at least `threshold_ms`, 0 disables them
+pub fn insert_markers(lyrics_data: &mut LyricsData, threshold_ms: u64) {
+    lyrics_data.lyrics.retain(|line| line.kind == LineKind::Lyric);
+    if threshold_ms == 0 || !lyrics_data.is_synced || lyrics_data.lyrics.is_empty() {
+        return;
+    }
+    let threshold_ms = threshold_ms as i64;
+
+    let mut lines = Vec::with_capacity(lyrics_data.lyrics.len() + 2);
+    let first_start = lyrics_data.lyrics[0].start_time;
+    if first_start >= threshold_ms {
+        lines.push(marker(LineKind::Intro, 0, first_start));
+    }
+
+    let mut lyrics = std::mem::take(&mut lyrics_data.lyrics).into_iter().peekable();
+    while let Some(line) = lyrics.next() {
+        let line_end = line.end_time.unwrap_or(line.start_time + ASSUMED_LINE_MS);
+        // Outros run until the end of the track
+        let gap_end = match lyrics.peek() {
+            Some(next) => next.start_time,
+            None => lyrics_data.track.duration as i64,
+        };
+        lines.push(line);
+        if gap_end - line_end >= threshold_ms {
+            lines.push(marker(LineKind::Interlude, line_end, gap_end));
+        }
+    }
+    lyrics_data.lyrics = lines;
+}
+
+fn marker(kind: LineKind, start_time: i64, end_time: i64) -> LyricLine {
+    LyricLine {
+        start_time,
+        end_time: Some(end_time),
+        text: String::new(),
+        pron_text: None,
+        trans_text: None,
+        translations: Default::default(),
+        segments: Vec::new(),
+        singer: None,
+        background_text: None,
+        kind,
+        payload_indexes: Vec::new(),
+    }
+}
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::broadcast;
+
+use crate::ws::{self, ServerMessage};
+use crate::AppState;
+
+// Local IPC transport: newline-delimited JSON frames, same messages as /ws.
+// No token is required: the Unix socket is 0600, but the Windows pipe keeps the
+// default DACL, which does not limit it to the current user.
+
+#[cfg(unix)]
+const SOCKET_FILE_NAME: &str = "lyrics-plus-overlay.sock";
+#[cfg(windows)]
+const PIPE_NAME: &str = r"\\.\pipe\lyrics-plus-overlay";
+
+// Default endpoint: a socket in the app data dir, or a named pipe on Windows
+pub fn default_path<R: Runtime>(app: &AppHandle<R>) -> Option<String> {
+    #[cfg(unix)]
+    {
+        app.path()
+            .app_data_dir()
+            .ok()
+            .map(|dir| dir.join(SOCKET_FILE_NAME).to_string_lossy().into_owned())
+    }
+    #[cfg(windows)]
+    {
+        let _ = app;
+        Some(PIPE_NAME.to_string())
+    }
+}
+
+pub struct IpcListener {
+    path: String,
+    #[cfg(unix)]
+    listener: tokio::net::UnixListener,
+    #[cfg(windows)]
+    pipe: tokio::net::windows::named_pipe::NamedPipeServer,
+}
+
+impl IpcListener {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+#[cfg(unix)]
+pub fn bind(path: &str) -> std::io::Result<IpcListener> {
+    use std::io::{Error, ErrorKind};
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+
+    let target = std::path::Path::new(path);
+    let parent = target.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(std::path::Path::new("."));
+    std::fs::create_dir_all(parent)?;
+
+    // A previous run may have left the socket file behind, but a live one belongs to another
+    // instance and anything else at the path (a mistyped ipcPath) isn't ours to delete
+    match std::fs::symlink_metadata(target) {
+        Ok(metadata) if !metadata.file_type().is_socket() => {
+            return Err(Error::new(ErrorKind::AlreadyExists, format!("{} exists and is not a socket", path)));
+        }
+        Ok(_) => match std::os::unix::net::UnixStream::connect(target) {
+            Ok(_) => {
+                return Err(Error::new(ErrorKind::AddrInUse, format!("IPC socket {} is in use by another process", path)));
+            }
+            Err(e) if e.kind() == ErrorKind::ConnectionRefused => std::fs::remove_file(target)?,
+            Err(e) => return Err(e),
+        },
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+
+    // Bind inside a private 0700 directory and move the socket into place once it is 0600,
+    // so nobody else can connect while the permissions are still the umask's
+    let staging = parent.join(format!(".{}.{}", SOCKET_FILE_NAME, std::process::id()));
+    std::fs::DirBuilder::new().mode(0o700).create(&staging)?;
+    let staged = staging.join(SOCKET_FILE_NAME);
+    let result = tokio::net::UnixListener::bind(&staged).and_then(|listener| {
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))?;
+        std::fs::rename(&staged, target)?;
+        Ok(listener)
+    });
+    let _ = std::fs::remove_file(&staged);
+    let _ = std::fs::remove_dir(&staging);
+    Ok(IpcListener { path: path.to_string(), listener: result? })
+}
+
+#[cfg(windows)]
+pub fn bind(path: &str) -> std::io::Result<IpcListener> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    // first_pipe_instance fails if another process already owns the name
+    let pipe = ServerOptions::new().first_pipe_instance(true).create(path)?;
+    Ok(IpcListener { path: path.to_string(), pipe })
+}
+
+// Accept connections forever, only returns on error
+#[cfg(unix)]
+pub async fn serve<R: Runtime>(ipc: IpcListener, state: Arc<AppState<R>>) -> Result<(), String> {
+    loop {
+        let (stream, _) = ipc.listener.accept().await.map_err(|e| format!("IPC accept failed: {}", e))?;
+        tokio::spawn(handle_connection(stream, state.clone()));
+    }
+}
+
+#[cfg(windows)]
+pub async fn serve<R: Runtime>(ipc: IpcListener, state: Arc<AppState<R>>) -> Result<(), String> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let mut pipe = ipc.pipe;
+    loop {
+        pipe.connect().await.map_err(|e| format!("IPC accept failed: {}", e))?;
+        // Create the next instance before handing this one off so clients never see the pipe missing
+        let next = ServerOptions::new().create(&ipc.path).map_err(|e| format!("IPC pipe creation failed: {}", e))?;
+        let connected = std::mem::replace(&mut pipe, next);
+        tokio::spawn(handle_connection(connected, state.clone()));
+    }
+}
+
+async fn handle_connection<R: Runtime, S>(stream: S, state: Arc<AppState<R>>)
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    let mut pushed = state.client_push.subscribe();
+
+    if let Some(message) = ws::greeting(&state) {
+        if send(&mut writer, &message).await.is_err() {
+            return;
+        }
+    }
+
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let line = match line {
+                    Ok(Some(line)) => line,
+                    Ok(None) | Err(_) => break,
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                let reply = ws::handle_text(&state, &line);
+                if send(&mut writer, &reply).await.is_err() {
+                    break;
+                }
+            }
+            outgoing = pushed.recv() => {
+                match outgoing {
+                    Ok(message) => {
+                        if send(&mut writer, &message).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }
+    }
+}
+
+async fn send<W: AsyncWrite + Unpin>(writer: &mut W, message: &ServerMessage) -> std::io::Result<()> {
+    let mut frame = serde_json::to_vec(message)?;
+    frame.push(b'\n');
+    writer.write_all(&frame).await?;
+    writer.flush().await
+}
@@ -0,0 +1,91 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::ProgressData;
+
+// Weight of a new sample in the smoothed delay, low enough to ride out single slow requests
+const SMOOTHING: f64 = 0.2;
+// Delays above this come from a wrong clock or a stuck client, not from transport
+const MAX_DELAY_MS: i64 = 5000;
+
+pub type SharedLatency = Arc<Mutex<LatencyCompensator>>;
+
+// Returned by the get_latency_info command
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyInfo {
+    pub delay_ms: Option<f64>, // Smoothed delay between `sentAt` and arrival, None until measured
+    pub offset_ms: i64,
+    pub track_offset_ms: i64,
+    pub samples: u64,
+}
+
+// Shifts incoming positions by the measured delivery delay plus the user's offset
+#[derive(Debug, Default)]
+pub struct LatencyCompensator {
+    delay_ms: Option<f64>,
+    samples: u64,
+    pub offset_ms: i64, // Manual fine-tuning, positive shows lyrics earlier
+    pub track_offset_ms: i64, // Offset stored for the current track, see track_offsets.rs
+}
+
+impl LatencyCompensator {
+    pub fn new(offset_ms: i64) -> Self {
+        Self { offset_ms, ..Default::default() }
+    }
+
+    pub fn info(&self) -> LatencyInfo {
+        LatencyInfo {
+            delay_ms: self.delay_ms,
+            offset_ms: self.offset_ms,
+            track_offset_ms: self.track_offset_ms,
+            samples: self.samples,
+        }
+    }
+
+    pub fn compensate(&mut self, progress_data: &mut ProgressData) {
+        if let Some(sent_at) = progress_data.sent_at {
+            self.record(now_ms() - sent_at as i64);
+        }
+
+        // The position only moved on in the meantime if the track is playing
+        let delay = if progress_data.is_playing {
+            self.delay_ms.unwrap_or(0.0).round() as i64
+        } else {
+            0
+        };
+        let offset = self.offset_ms + self.track_offset_ms;
+        let mut position = (progress_data.position as i64 + delay + offset).max(0) as u64;
+        if let Some(duration) = progress_data.duration.filter(|&d| d > 0) {
+            position = position.min(duration);
+        }
+        progress_data.position = position;
+    }
+
+    // Inverse of `compensate` for a position on the overlay's timeline, e.g. a seek target
+    pub fn to_player_position(&self, position_ms: u64) -> u64 {
+        let delay = self.delay_ms.unwrap_or(0.0).round() as i64;
+        let offset = self.offset_ms + self.track_offset_ms;
+        (position_ms as i64 - delay - offset).max(0) as u64
+    }
+
+    fn record(&mut self, delay: i64) {
+        if !(0..=MAX_DELAY_MS).contains(&delay) {
+            return;
+        }
+        let delay = delay as f64;
+        self.delay_ms = Some(match self.delay_ms {
+            Some(smoothed) => smoothed + SMOOTHING * (delay - smoothed),
+            None => delay,
+        });
+        self.samples += 1;
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
@@ -1,12 +1,14 @@
 use axum::{
-    routing::post,
+    extract::rejection::JsonRejection,
+    routing::{get, post},
     Json, Router,
     http::Method,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tauri::{AppHandle, Emitter, Runtime, Manager, PhysicalPosition};
+use tauri::{AppHandle, Emitter, Runtime, Manager};
 use tower_http::cors::{Any, CorsLayer};
 
 use tauri::menu::{Menu, MenuItem};
@@ -16,10 +18,56 @@ use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
 #[cfg(target_os = "windows")]
 use windows::Win32::Foundation::POINT;
 
+mod api;
+mod auth;
+mod auto_timing;
+mod clock;
+mod commands;
+mod config;
+mod detect;
+mod events;
+mod interludes;
+mod ipc;
+mod latency;
+mod normalize;
+mod now_playing;
+mod origin;
+mod patch;
+mod remote;
+mod server;
+mod sources;
+mod status;
+mod throttle;
+mod track_offsets;
+mod translations;
+mod validation;
+mod ws;
+
+use api::{Ack, ApiError};
+use auth::{AuthState, SharedAuth};
+use auto_timing::{SharedUnsyncedTiming, UnsyncedTiming};
+use clock::{ClockSnapshot, SharedPlaybackClock};
+use commands::{PlayerCommand, QueuedCommand, SharedCommandQueue};
+use config::{BackendConfig, ServerInfo};
+use detect::SharedPlaybackDetector;
+use events::{BusEvent, EventBus};
+use latency::{LatencyCompensator, LatencyInfo, SharedLatency};
+use now_playing::{Forward, NowPlaying, SharedNowPlaying, StateSnapshot};
+use origin::{OriginPolicy, OriginStats, SharedOriginPolicy};
+use patch::LyricsPatch;
+use server::{ServerControl, ServerStatus, SharedServerStatus, TrayServerItem};
+use sources::{ArbitrationPolicy, SourceInfo};
+use throttle::{SharedProgressThrottle, ThrottleStats};
+use track_offsets::{SharedTrackOffsets, TrackOffsetInfo, TrackOffsets};
+use translations::SharedTranslationLanguages;
+use ws::ClientPush;
+
 // Track info from Spotify
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TrackInfo {
+    #[serde(default)]
+    pub id: Option<String>, // Player's track id (e.g. Spotify URI), used to address PATCH /lyrics
     pub title: String,
     pub artist: String,
     pub album: String,
@@ -37,7 +85,52 @@ pub struct LyricLine {
     #[serde(default)]
     pub pron_text: Option<String>,  // Phonetic/romanized text
     #[serde(default)]
-    pub trans_text: Option<String>, // Translation text
+    pub trans_text: Option<String>, // Translation text, kept as an alias of the preferred entry in `translations`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, String>, // Translations keyed by language tag (e.g. "ko", "en")
+    #[serde(default)]
+    pub singer: Option<String>, // Vocalist/part singing the line (e.g. "v1", "v2"), None for a single singer
+    #[serde(default)]
+    pub background_text: Option<String>, // Backing vocals sung alongside the main text
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<LyricSegment>, // Word/syllable timing for karaoke highlighting, empty when not provided
+    #[serde(default, skip_serializing_if = "LineKind::is_lyric")]
+    pub kind: LineKind,
+    #[serde(skip)]
+    pub payload_indexes: Vec<usize>, // Where the line was in the payload as sent (several after a merge, none for markers), see normalize.rs
+}
+
+impl LyricLine {
+    pub fn payload_index(&self) -> Option<usize> {
+        self.payload_indexes.first().copied()
+    }
+}
+
+// Lines inserted by interludes.rs have no text, the overlay renders them itself
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LineKind {
+    #[default]
+    Lyric,
+    Intro, // Before the first line, `endTime` is when singing starts
+    Interlude, // Instrumental gap (solo, bridge, outro)
+}
+
+impl LineKind {
+    fn is_lyric(&self) -> bool {
+        *self == LineKind::Lyric
+    }
+}
+
+// Timed part of a line (Spotify syllable sync / Enhanced LRC word tags)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricSegment {
+    pub text: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    #[serde(default)]
+    pub singer: Option<String>, // Overrides the line's singer, for parts sung by someone else
 }
 
 // Full lyrics data payload
@@ -47,6 +140,31 @@ pub struct LyricsData {
     pub track: TrackInfo,
     pub lyrics: Vec<LyricLine>,
     pub is_synced: bool,
+    #[serde(default)]
+    pub source: Option<String>, // Player sending the data, see sources.rs
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub estimated_timing: Option<UnsyncedTiming>, // Set when the times of unsynced lyrics were made up, see auto_timing.rs
+}
+
+impl LyricsData {
+    pub fn source_id(&self) -> &str {
+        self.source.as_deref().unwrap_or(sources::DEFAULT_SOURCE_ID)
+    }
+
+    // Synced, or unsynced with estimated times
+    pub fn has_timing(&self) -> bool {
+        self.is_synced || self.estimated_timing.is_some()
+    }
+
+    // Falls back to the title for players that don't send an id
+    pub fn track_id(&self) -> &str {
+        self.track.id.as_deref().unwrap_or(&self.track.title)
+    }
+
+    // Index in `lyrics` of the line sent at `payload_index`, lines merged away resolve to the one they joined
+    pub fn stored_index(&self, payload_index: usize) -> Option<usize> {
+        self.lyrics.iter().position(|line| line.payload_indexes.contains(&payload_index))
+    }
 }
 
 // Progress sync data
@@ -61,6 +179,16 @@ pub struct ProgressData {
     pub remaining: Option<f64>,
     #[serde(default)]
     pub next_track: Option<NextTrackInfo>,
+    #[serde(default)]
+    pub source: Option<String>, // Player sending the data, see sources.rs
+    #[serde(default)]
+    pub sent_at: Option<u64>, // Epoch ms when the player sampled `position`, used for latency compensation
+}
+
+impl ProgressData {
+    pub fn source_id(&self) -> &str {
+        self.source.as_deref().unwrap_or(sources::DEFAULT_SOURCE_ID)
+    }
 }
 
 // Next track info for preview
@@ -88,6 +216,13 @@ pub struct ProgressEvent {
 // Shared state for HTTP server
 struct AppState<R: Runtime> {
     app_handle: AppHandle<R>,
+    now_playing: SharedNowPlaying,
+    client_push: ClientPush, // Messages pushed to WebSocket clients
+    events: EventBus, // Events published to SSE consumers
+    auth: SharedAuth,
+    origins: SharedOriginPolicy,
+    interlude_threshold_ms: u64, // Gaps at least this long get an interlude marker
+    full_width_punctuation: bool, // Convert full-width punctuation while normalizing lyrics
 }
 
 // Internal state for lock logic
@@ -101,49 +236,399 @@ struct AppLockState {
     auto_lock_delay: f32, // Delay in seconds before auto-locking (when no movement after unlock)
 }
 
+// Validate, store and forward incoming data, shared by the HTTP and WebSocket transports
+fn ingest_lyrics<R: Runtime>(state: &AppState<R>, mut lyrics_data: LyricsData) -> Result<Ack, ApiError> {
+    // Fix what players commonly get wrong before judging the payload
+    let mut normalized = normalize::normalize(&mut lyrics_data, state.full_width_punctuation);
+    let errors = validation::validate_lyrics(&lyrics_data);
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+    // SSE and GET /state consumers that only know `transText` still get a translation
+    let languages = translations::selected_languages(&state.app_handle);
+    for line in &mut lyrics_data.lyrics {
+        translations::fill_alias(line, &languages);
+    }
+
+    let track = lyrics_data.track.title.clone();
+    let lines = lyrics_data.lyrics.len();
+    let is_synced = lyrics_data.is_synced;
+    let source = lyrics_data.source_id().to_string();
+    // Counted before the markers, `lines` is what the player sent minus what normalization dropped
+    auto_timing::estimate(&mut lyrics_data, auto_timing::current(&state.app_handle));
+    interludes::insert_markers(&mut lyrics_data, state.interlude_threshold_ms);
+    normalized.extend(normalize::fill_end_times(&mut lyrics_data));
+
+    let forwards = state.now_playing.lock()
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .receive_lyrics(lyrics_data);
+    let forwarded = forwards.iter().any(|f| matches!(f, Forward::Lyrics(_)));
+    forward(&state.app_handle, forwards);
+
+    Ok(Ack::Lyrics { track, lines, is_synced, source, forwarded, normalized })
+}
+
+fn ingest_progress<R: Runtime>(state: &AppState<R>, mut progress_data: ProgressData) -> Result<Ack, ApiError> {
+    let errors = validation::validate_progress(&progress_data);
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+
+    // Everything downstream (overlay, clock, SSE) sees the compensated position
+    if let Some(latency) = state.app_handle.try_state::<SharedLatency>() {
+        if let Ok(mut l) = latency.lock() {
+            l.compensate(&mut progress_data);
+        }
+    }
+
+    let position = progress_data.position;
+    let is_playing = progress_data.is_playing;
+    let source = progress_data.source_id().to_string();
+
+    let forwards = state.now_playing.lock()
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .receive_progress(progress_data);
+    let forwarded = forwards.iter().any(|f| matches!(f, Forward::Progress(_)));
+    forward(&state.app_handle, forwards);
+
+    Ok(Ack::Progress { position, is_playing, source, forwarded })
+}
+
+fn ingest_lyrics_patch<R: Runtime>(state: &AppState<R>, mut patch: LyricsPatch) -> Result<Ack, ApiError> {
+    // Late translations come from the same players, with the same decomposed Hangul and whitespace
+    normalize::normalize_patch(&mut patch, state.full_width_punctuation);
+    let errors = validation::validate_patch(&patch);
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+
+    let forwards = state.now_playing.lock()
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .patch_lyrics(&patch, &translations::selected_languages(&state.app_handle))?;
+    let forwarded = !forwards.is_empty();
+    forward(&state.app_handle, forwards);
+
+    Ok(Ack::LyricsPatch {
+        track_id: patch.track_id.clone(),
+        lines: patch.lines.len(),
+        source: patch.source_id().to_string(),
+        forwarded,
+    })
+}
+
+// Emit data that passed arbitration to the frontend and external consumers
+fn forward<R: Runtime>(app: &AppHandle<R>, forwards: Vec<Forward>) {
+    for f in forwards {
+        match f {
+            Forward::Lyrics(lyrics_data) => {
+                if let Some(clock) = app.try_state::<SharedPlaybackClock>() {
+                    clock.set_lyrics(&lyrics_data);
+                }
+                apply_track_offset(app, &lyrics_data.track);
+                let detected = app.try_state::<SharedPlaybackDetector>()
+                    .and_then(|detector| detector.lock().ok().map(|mut d| d.on_lyrics(&lyrics_data)));
+                emit_playback_events(app, detected.unwrap_or_default());
+                // The overlay gets the selected translations, external consumers all of them
+                let overlay_data = translations::lyrics_for_overlay(app, &lyrics_data);
+                let _ = app.emit("lyrics-update", LyricsEvent { lyrics_data: overlay_data });
+                events::publish(app, BusEvent::Lyrics(LyricsEvent { lyrics_data }));
+            }
+            Forward::Progress(progress_data) => {
+                if let Some(clock) = app.try_state::<SharedPlaybackClock>() {
+                    clock.set_progress(&progress_data);
+                }
+                let detected = app.try_state::<SharedPlaybackDetector>()
+                    .and_then(|detector| detector.lock().ok().map(|mut d| d.on_progress(&progress_data)));
+                emit_playback_events(app, detected.unwrap_or_default());
+                // External consumers get every update, the webview a throttled stream
+                throttle::emit_progress(app, progress_data.clone());
+                events::publish(app, BusEvent::Progress(ProgressEvent { progress_data }));
+            }
+            Forward::LyricsPatch(event) => {
+                let overlay_event = translations::patch_for_overlay(&event, &translations::selected_languages(app));
+                let _ = app.emit("lyrics-patch", overlay_event);
+                events::publish(app, BusEvent::LyricsPatch(event));
+            }
+            Forward::SourceChanged(source) => {
+                if let Some(throttle) = app.try_state::<SharedProgressThrottle>() {
+                    if let Ok(mut t) = throttle.lock() {
+                        t.reset();
+                    }
+                }
+                let _ = app.emit("source-change", source.clone());
+                events::publish(app, BusEvent::SourceChange(source));
+            }
+            Forward::Cleared(cleared) => {
+                if let Some(clock) = app.try_state::<SharedPlaybackClock>() {
+                    clock.clear(cleared.lyrics, cleared.progress);
+                }
+                let _ = app.emit("now-playing-cleared", cleared);
+                events::publish(app, BusEvent::Cleared(cleared));
+            }
+        }
+    }
+}
+
+// Use the stored offset of the track now shown for the following progress updates
+fn apply_track_offset<R: Runtime>(app: &AppHandle<R>, track: &TrackInfo) {
+    let offset_ms = match app.try_state::<SharedTrackOffsets>() {
+        Some(offsets) => offsets.lock().map(|o| o.get(track)).unwrap_or(0),
+        None => 0,
+    };
+    if let Some(latency) = app.try_state::<SharedLatency>() {
+        if let Ok(mut l) = latency.lock() {
+            l.track_offset_ms = offset_ms;
+        }
+    }
+}
+
+// Change the current track's offset, shared by the nudge/reset commands
+fn update_track_offset<R: Runtime>(
+    app: &AppHandle<R>,
+    change: impl FnOnce(i64) -> i64,
+) -> Result<TrackOffsetInfo, String> {
+    let track = app.state::<SharedNowPlaying>().lock().map_err(|e| e.to_string())?
+        .lyrics.as_ref().map(|l| l.track.clone())
+        .ok_or("No track playing")?;
+
+    let offset_ms = {
+        let offsets = app.state::<SharedTrackOffsets>();
+        let mut offsets = offsets.lock().map_err(|e| e.to_string())?;
+        let offset_ms = change(offsets.get(&track));
+        offsets.set(&track, offset_ms)?;
+        offset_ms
+    };
+    apply_track_offset(app, &track);
+
+    Ok(TrackOffsetInfo {
+        track: Some(format!("{} - {}", track.artist, track.title)),
+        offset_ms,
+    })
+}
+
+// Emit seek/stall/track-change to the frontend and external consumers
+fn emit_playback_events<R: Runtime>(app: &AppHandle<R>, detected: Vec<detect::PlaybackEvent>) {
+    for event in detected {
+        event.emit(app);
+        events::publish(app, BusEvent::Playback(event));
+    }
+}
+
+// Emit a lock state change to the frontend and external consumers
+fn emit_lock_state<R: Runtime>(app: &AppHandle<R>, locked: bool) {
+    let _ = app.emit("lock-state-update", locked);
+    events::publish(app, BusEvent::LockState(locked));
+}
+
 // HTTP endpoint handlers
 async fn handle_lyrics<R: Runtime>(
     axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
-    Json(lyrics_data): Json<LyricsData>,
-) -> &'static str {
-    // Emit to frontend
-    let _ = state.app_handle.emit("lyrics-update", LyricsEvent { lyrics_data });
-    "OK"
+    payload: Result<Json<LyricsData>, JsonRejection>,
+) -> Result<Json<Ack>, ApiError> {
+    let Json(lyrics_data) = payload?;
+    ingest_lyrics(&state, lyrics_data).map(Json)
+}
+
+async fn handle_lyrics_patch<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+    payload: Result<Json<LyricsPatch>, JsonRejection>,
+) -> Result<Json<Ack>, ApiError> {
+    let Json(patch) = payload?;
+    ingest_lyrics_patch(&state, patch).map(Json)
 }
 
 async fn handle_progress<R: Runtime>(
     axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
-    Json(progress_data): Json<ProgressData>,
-) -> &'static str {
-    // Emit to frontend
-    let _ = state.app_handle.emit("progress-update", ProgressEvent { progress_data });
-    "OK"
+    payload: Result<Json<ProgressData>, JsonRejection>,
+) -> Result<Json<Ack>, ApiError> {
+    let Json(progress_data) = payload?;
+    ingest_progress(&state, progress_data).map(Json)
+}
+
+async fn handle_state<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+) -> Result<Json<StateSnapshot>, ApiError> {
+    let now_playing = state.now_playing.lock().map_err(|e| ApiError::internal(e.to_string()))?;
+    Ok(Json(now_playing.snapshot()))
+}
+
+// Routes of the current protocol version, see status::ROUTES
+fn api_routes<R: Runtime>() -> Router<Arc<AppState<R>>> {
+    Router::new()
+        .route("/status", get(status::handle_status::<R>))
+        .route("/lyrics", post(handle_lyrics::<R>).patch(handle_lyrics_patch::<R>))
+        .route("/progress", post(handle_progress::<R>))
+        .route("/state", get(handle_state::<R>))
+        .route("/ws", get(ws::handle_ws::<R>))
+        .route("/events", get(events::handle_events::<R>))
+        .route("/commands", get(commands::handle_poll::<R>))
+        .route("/commands/:id/ack", post(commands::handle_ack::<R>))
+        .route("/control/show", post(remote::handle_show::<R>))
+        .route("/control/hide", post(remote::handle_hide::<R>))
+        .route("/control/lock", post(remote::handle_lock::<R>))
+        .route("/control/unlock", post(remote::handle_unlock::<R>))
+        .route("/control/toggle-lock", post(remote::handle_toggle_lock::<R>))
+        .route("/control/position", post(remote::handle_position::<R>))
+        .route("/control/settings", post(remote::handle_settings::<R>))
+        .route("/control/profile", post(remote::handle_profile::<R>))
+}
+
+// Bind to the configured host, falling back to the next free port when the preferred one is taken
+async fn bind_listener(config: &BackendConfig) -> std::io::Result<tokio::net::TcpListener> {
+    let mut last_err = None;
+    for port in config.candidate_ports() {
+        match tokio::net::TcpListener::bind((config.host.as_str(), port)).await {
+            Ok(listener) => return Ok(listener),
+            Err(e) => {
+                eprintln!("Failed to bind to {}:{}: {}", config.host, port, e);
+                last_err = Some(e);
+            }
+        }
+    }
+    Err(last_err.unwrap_or_else(|| std::io::Error::new(std::io::ErrorKind::AddrInUse, "No port available")))
+}
+
+// Run the server until `shutdown` resolves (Ok) or binding/serving fails (Err), see server::supervise
+// Settings setup() reads once, re-applied on every (re)start so edits to backend.json take effect
+fn reload_settings<R: Runtime>(app: &AppHandle<R>, config: &BackendConfig) {
+    if let Ok(mut auth) = app.state::<SharedAuth>().lock() {
+        auth.required = config.require_token;
+    }
+    if let Ok(mut latency) = app.state::<SharedLatency>().lock() {
+        latency.offset_ms = config.position_offset_ms;
+    }
+    if let Ok(mut timing) = app.state::<SharedUnsyncedTiming>().lock() {
+        *timing = config.unsynced_timing;
+    }
+
+    let forwards = match app.state::<SharedNowPlaying>().lock() {
+        Ok(mut now_playing) => {
+            now_playing.sources.policy = config.source_policy.clone();
+            now_playing.rearbitrate()
+        }
+        Err(_) => Vec::new(),
+    };
+    forward(app, forwards);
+
+    let languages = translations::normalize_languages(config.translation_languages.clone());
+    let changed = match app.state::<SharedTranslationLanguages>().lock() {
+        Ok(mut selected) if *selected != languages => {
+            *selected = languages.clone();
+            true
+        }
+        _ => false,
+    };
+    if changed {
+        let _ = app.emit("translation-languages", languages.clone());
+        let lyrics_data = app.state::<SharedNowPlaying>().lock().ok().and_then(|n| n.lyrics.clone());
+        if let Some(lyrics_data) = lyrics_data {
+            let lyrics_data = translations::for_overlay(&lyrics_data, &languages);
+            let _ = app.emit("lyrics-update", LyricsEvent { lyrics_data });
+        }
+    }
 }
 
-// Start HTTP server
-async fn start_http_server<R: Runtime>(app_handle: AppHandle<R>) {
-    let state = Arc::new(AppState { app_handle });
+// Depending on `config.transport` this serves HTTP on TCP, the local IPC endpoint, or both
+async fn run_server<R: Runtime>(
+    app_handle: AppHandle<R>,
+    config: BackendConfig,
+    shutdown: impl std::future::Future<Output = ()> + Send + 'static,
+) -> Result<(), String> {
+    let now_playing = app_handle.state::<SharedNowPlaying>().inner().clone();
+    let client_push = app_handle.state::<ClientPush>().inner().clone();
+    let events = app_handle.state::<EventBus>().inner().clone();
+    let auth = app_handle.state::<SharedAuth>().inner().clone();
+    reload_settings(&app_handle, &config);
+    let origins = app_handle.state::<SharedOriginPolicy>().inner().clone();
+    origins.set_allowed(config.allowed_origins.clone());
+    origins.set_bind_host(&config.host);
+    if let Ok(mut t) = app_handle.state::<SharedProgressThrottle>().lock() {
+        t.set_max_rate(config.progress_max_rate);
+    }
+    let state = Arc::new(AppState {
+        app_handle: app_handle.clone(),
+        now_playing,
+        client_push,
+        events,
+        auth,
+        origins: origins.clone(),
+        interlude_threshold_ms: config.interlude_threshold_ms,
+        full_width_punctuation: config.full_width_punctuation,
+    });
 
     let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods([Method::POST, Method::GET, Method::OPTIONS])
+        .allow_origin(OriginPolicy::cors_origin(origins))
+        .allow_methods([Method::POST, Method::PATCH, Method::GET, Method::OPTIONS])
         .allow_headers(Any);
 
+    // Unversioned routes are kept for older extensions, new clients should use /v1
     let app = Router::new()
-        .route("/lyrics", post(handle_lyrics::<R>))
-        .route("/progress", post(handle_progress::<R>))
+        .merge(api_routes::<R>())
+        .nest("/v1", api_routes::<R>())
+        .layer(axum::middleware::from_fn_with_state(state.clone(), auth::require_token::<R>))
         .layer(cors)
-        .with_state(state);
+        .layer(axum::middleware::from_fn_with_state(state.clone(), origin::reject_unknown_origins::<R>))
+        .with_state(state.clone());
+
+    let listener = if config.transport.uses_tcp() {
+        let listener = bind_listener(&config).await
+            .map_err(|e| format!("Could not bind to {}:{}: {}", config.host, config.port, e))?;
+        Some(listener)
+    } else {
+        None
+    };
+    let addr = match &listener {
+        Some(listener) => Some(listener.local_addr()
+            .map_err(|e| format!("Could not read local address: {}", e))?),
+        None => None,
+    };
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:15000")
-        .await
-        .expect("Failed to bind to port 15000");
+    let ipc_listener = if config.transport.uses_ipc() {
+        let path = config.ipc_path.clone()
+            .or_else(|| ipc::default_path(&app_handle))
+            .ok_or("No IPC path available")?;
+        let ipc_listener = ipc::bind(&path)
+            .map_err(|e| format!("Could not open IPC endpoint {}: {}", path, e))?;
+        Some(ipc_listener)
+    } else {
+        None
+    };
 
-    println!("HTTP server listening on http://127.0.0.1:15000");
+    let info = ServerInfo::new(addr, ipc_listener.as_ref().map(|ipc| ipc.path().to_string()));
+    match config::write_discovery_file(&app_handle, &info) {
+        Ok(path) => println!("Discovery file written to {}", path.display()),
+        Err(e) => eprintln!("Failed to write discovery file: {}", e),
+    }
+    server::set_status(&app_handle, ServerStatus::Listening(info));
 
-    axum::serve(listener, app)
-        .await
-        .expect("HTTP server failed");
+    if let Some(addr) = addr {
+        println!("HTTP server listening on http://{}", addr);
+    }
+    if let Some(ipc_listener) = &ipc_listener {
+        println!("IPC server listening on {}", ipc_listener.path());
+    }
+
+    // A disabled transport never completes, so only the enabled ones can end the select below
+    let http = async move {
+        match listener {
+            Some(listener) => axum::serve(listener, app).await.map_err(|e| format!("Server failed: {}", e)),
+            None => std::future::pending().await,
+        }
+    };
+    let ipc = async move {
+        match ipc_listener {
+            Some(ipc_listener) => ipc::serve(ipc_listener, state).await,
+            None => std::future::pending().await,
+        }
+    };
+
+    // Not a graceful shutdown: open WebSocket/SSE/IPC connections would keep it waiting forever.
+    // Dropping the serve futures releases the port, existing connections finish on their own.
+    tokio::select! {
+        result = http => result,
+        result = ipc => result,
+        _ = shutdown => Ok(()),
+    }
 }
 
 // Tauri command to start dragging window
@@ -162,11 +647,14 @@ async fn set_ignore_cursor_events(window: tauri::Window, ignore: bool) -> Result
 // Tauri command to update lock state from frontend
 #[tauri::command]
 async fn set_lock_state(
+    app: AppHandle,
     state: tauri::State<'_, Arc<Mutex<AppLockState>>>,
     locked: bool
 ) -> Result<(), String> {
     let mut s = state.lock().map_err(|e| e.to_string())?;
     s.is_locked = locked;
+    // The frontend already knows, only tell external consumers
+    events::publish(&app, BusEvent::LockState(locked));
     Ok(())
 }
 
@@ -219,27 +707,269 @@ async fn set_auto_lock_delay(
 
 #[tauri::command]
 async fn open_settings_window(app: AppHandle) -> Result<(), String> {
-    // Check if settings window already exists
-    if let Some(settings_window) = app.get_webview_window("settings") {
-        settings_window.show().map_err(|e| e.to_string())?;
-        settings_window.set_focus().map_err(|e| e.to_string())?;
-        // Force reload to ensure correct query param
-        settings_window.eval("window.location.replace('index.html?settings=true')").map_err(|e| e.to_string())?;
-    } else {
-        // Create new settings window
-        let _settings_window = tauri::WebviewWindowBuilder::new(
-            &app,
-            "settings",
-            tauri::WebviewUrl::App("index.html?settings=true".into())
-        )
-        .title("Settings")
-        .inner_size(400.0, 600.0)
-        .resizable(true)
-        .decorations(true)
-        .always_on_top(true)
-        .build()
-        .map_err(|e| e.to_string())?;
+    remote::open_settings(&app)
+}
+
+// Tauri command to get the address the HTTP server is actually listening on
+#[tauri::command]
+async fn get_server_info(
+    state: tauri::State<'_, SharedServerStatus>
+) -> Result<Option<ServerInfo>, String> {
+    let s = state.lock().map_err(|e| e.to_string())?;
+    Ok(s.info())
+}
+
+// Tauri command to get the HTTP server state (starting / listening / error)
+#[tauri::command]
+async fn get_server_status(state: tauri::State<'_, SharedServerStatus>) -> Result<ServerStatus, String> {
+    let s = state.lock().map_err(|e| e.to_string())?;
+    Ok(s.clone())
+}
+
+// Tauri command to restart the HTTP server, e.g. after editing backend.json
+#[tauri::command]
+async fn restart_server(control: tauri::State<'_, Arc<ServerControl>>) -> Result<(), String> {
+    control.request_restart();
+    Ok(())
+}
+
+// Tauri command to get the latest lyrics/progress received from the player
+#[tauri::command]
+async fn get_current_state(
+    app: AppHandle,
+    state: tauri::State<'_, SharedNowPlaying>
+) -> Result<StateSnapshot, String> {
+    let mut snapshot = state.lock().map_err(|e| e.to_string())?.snapshot();
+    snapshot.lyrics_data = snapshot.lyrics_data.map(|l| translations::lyrics_for_overlay(&app, &l));
+    Ok(snapshot)
+}
+
+// Tauri command to ask connected WebSocket clients to resend lyrics/progress
+#[tauri::command]
+async fn request_resend(client_push: tauri::State<'_, ClientPush>) -> Result<usize, String> {
+    // Returns the number of clients reached (0 when none is connected)
+    Ok(client_push.send(ws::ServerMessage::ResendRequest).unwrap_or(0))
+}
+
+// Tauri command to send a command (seek, play/pause, ...) to the player extension
+#[tauri::command]
+async fn enqueue_player_command(app: AppHandle, command: PlayerCommand) -> Result<QueuedCommand, String> {
+    Ok(commands::enqueue(&app, command))
+}
+
+// Tauri command to get the extrapolated position and active line
+#[tauri::command]
+async fn get_playback_clock(clock: tauri::State<'_, SharedPlaybackClock>) -> Result<ClockSnapshot, String> {
+    clock.snapshot().ok_or_else(|| "Playback clock unavailable".to_string())
+}
+
+// Tauri command to get the measured delivery delay and the manual offset
+#[tauri::command]
+async fn get_latency_info(state: tauri::State<'_, SharedLatency>) -> Result<LatencyInfo, String> {
+    let s = state.lock().map_err(|e| e.to_string())?;
+    Ok(s.info())
+}
+
+// Tauri command to set the manual position offset, in memory only (called while dragging)
+#[tauri::command]
+async fn set_position_offset(
+    state: tauri::State<'_, SharedLatency>,
+    offset_ms: i64
+) -> Result<(), String> {
+    let mut s = state.lock().map_err(|e| e.to_string())?;
+    s.offset_ms = offset_ms;
+    Ok(())
+}
+
+// Tauri command to persist the current position offset in the backend config
+#[tauri::command]
+async fn save_position_offset(
+    app: AppHandle,
+    state: tauri::State<'_, SharedLatency>
+) -> Result<(), String> {
+    let offset_ms = state.lock().map_err(|e| e.to_string())?.offset_ms;
+    let mut config = BackendConfig::read(&app);
+    config.position_offset_ms = offset_ms;
+    config.save(&app)
+}
+
+// Tauri command to get the translation languages shown in the overlay, in display order
+#[tauri::command]
+async fn get_translation_languages(
+    state: tauri::State<'_, SharedTranslationLanguages>
+) -> Result<Vec<String>, String> {
+    let s = state.lock().map_err(|e| e.to_string())?;
+    Ok(s.clone())
+}
+
+// Tauri command to choose the translation languages (empty = all), persisted in the backend config
+#[tauri::command]
+async fn set_translation_languages(
+    app: AppHandle,
+    state: tauri::State<'_, SharedTranslationLanguages>,
+    now_playing: tauri::State<'_, SharedNowPlaying>,
+    languages: Vec<String>
+) -> Result<Vec<String>, String> {
+    let languages = translations::normalize_languages(languages);
+    let mut config = BackendConfig::read(&app);
+    config.translation_languages = languages.clone();
+    config.save(&app)?;
+    *state.lock().map_err(|e| e.to_string())? = languages.clone();
+
+    let _ = app.emit("translation-languages", languages.clone());
+    // Re-send the current lyrics with the new selection, no need to wait for the player
+    let lyrics_data = now_playing.lock().map_err(|e| e.to_string())?.lyrics.clone();
+    if let Some(lyrics_data) = lyrics_data {
+        let lyrics_data = translations::for_overlay(&lyrics_data, &languages);
+        let _ = app.emit("lyrics-update", LyricsEvent { lyrics_data });
     }
+    Ok(languages)
+}
+
+// Tauri command to get the offset stored for the current track
+#[tauri::command]
+async fn get_track_offset(
+    now_playing: tauri::State<'_, SharedNowPlaying>,
+    offsets: tauri::State<'_, SharedTrackOffsets>
+) -> Result<TrackOffsetInfo, String> {
+    let track = now_playing.lock().map_err(|e| e.to_string())?
+        .lyrics.as_ref().map(|l| l.track.clone());
+    let offsets = offsets.lock().map_err(|e| e.to_string())?;
+    Ok(TrackOffsetInfo {
+        offset_ms: track.as_ref().map(|t| offsets.get(t)).unwrap_or(0),
+        track: track.map(|t| format!("{} - {}", t.artist, t.title)),
+    })
+}
+
+// Tauri command to shift the current track's lyrics, positive shows them earlier
+#[tauri::command]
+async fn nudge_track_offset(app: AppHandle, delta_ms: i64) -> Result<TrackOffsetInfo, String> {
+    update_track_offset(&app, |offset| offset + delta_ms)
+}
+
+// Tauri command to drop the current track's offset
+#[tauri::command]
+async fn reset_track_offset(app: AppHandle) -> Result<TrackOffsetInfo, String> {
+    update_track_offset(&app, |_| 0)
+}
+
+// Tauri command to get the progress throttle counters (diagnostics)
+#[tauri::command]
+async fn get_progress_stats(state: tauri::State<'_, SharedProgressThrottle>) -> Result<ThrottleStats, String> {
+    let s = state.lock().map_err(|e| e.to_string())?;
+    Ok(s.stats())
+}
+
+// Tauri command to get how many browser requests were rejected and the last origin
+#[tauri::command]
+async fn get_origin_stats(state: tauri::State<'_, SharedOriginPolicy>) -> Result<OriginStats, String> {
+    Ok(state.stats())
+}
+
+// Tauri command to get the ingest server token and whether it is enforced
+#[tauri::command]
+async fn get_auth_info(state: tauri::State<'_, SharedAuth>) -> Result<AuthState, String> {
+    let s = state.lock().map_err(|e| e.to_string())?;
+    Ok(s.clone())
+}
+
+// Tauri command to replace the ingest server token (clients must be updated)
+#[tauri::command]
+async fn regenerate_auth_token(
+    app: AppHandle,
+    state: tauri::State<'_, SharedAuth>
+) -> Result<String, String> {
+    let token = auth::generate_token();
+    auth::save_token(&app, &token)?;
+    let mut s = state.lock().map_err(|e| e.to_string())?;
+    s.token = token.clone();
+    Ok(token)
+}
+
+// Tauri command to enable/disable token enforcement, persisted in the backend config
+#[tauri::command]
+async fn set_token_required(
+    app: AppHandle,
+    state: tauri::State<'_, SharedAuth>,
+    required: bool
+) -> Result<(), String> {
+    let mut config = BackendConfig::read(&app);
+    config.require_token = required;
+    config.save(&app)?;
+    let mut s = state.lock().map_err(|e| e.to_string())?;
+    s.required = required;
+    Ok(())
+}
+
+// Tauri command to list the players that have sent data
+#[tauri::command]
+async fn get_sources(state: tauri::State<'_, SharedNowPlaying>) -> Result<Vec<SourceInfo>, String> {
+    let s = state.lock().map_err(|e| e.to_string())?;
+    Ok(s.sources.infos())
+}
+
+// Tauri command to get how unsynced lyrics are timed
+#[tauri::command]
+async fn get_unsynced_timing(state: tauri::State<'_, SharedUnsyncedTiming>) -> Result<UnsyncedTiming, String> {
+    let s = state.lock().map_err(|e| e.to_string())?;
+    Ok(*s)
+}
+
+// Tauri command to change how unsynced lyrics are timed, re-timing the current ones
+#[tauri::command]
+async fn set_unsynced_timing(
+    app: AppHandle,
+    state: tauri::State<'_, SharedUnsyncedTiming>,
+    now_playing: tauri::State<'_, SharedNowPlaying>,
+    timing: UnsyncedTiming
+) -> Result<(), String> {
+    let mut config = BackendConfig::read(&app);
+    config.unsynced_timing = timing;
+    config.save(&app)?;
+    *state.lock().map_err(|e| e.to_string())? = timing;
+
+    let forwards = {
+        let mut s = now_playing.lock().map_err(|e| e.to_string())?;
+        match s.lyrics.as_mut().filter(|l| !l.is_synced) {
+            Some(lyrics_data) => {
+                auto_timing::estimate(lyrics_data, timing);
+                let lyrics_data = lyrics_data.clone();
+                if let Some(stored) = s.sources.lyrics_mut(lyrics_data.source_id()) {
+                    *stored = lyrics_data.clone();
+                }
+                vec![Forward::Lyrics(lyrics_data)]
+            }
+            None => Vec::new(),
+        }
+    };
+    forward(&app, forwards);
+    Ok(())
+}
+
+// Tauri command to get how the overlay picks between several players
+#[tauri::command]
+async fn get_source_policy(state: tauri::State<'_, SharedNowPlaying>) -> Result<ArbitrationPolicy, String> {
+    let s = state.lock().map_err(|e| e.to_string())?;
+    Ok(s.sources.policy.clone())
+}
+
+// Tauri command to change how the overlay picks between several players
+#[tauri::command]
+async fn set_source_policy(
+    app: AppHandle,
+    state: tauri::State<'_, SharedNowPlaying>,
+    policy: ArbitrationPolicy
+) -> Result<(), String> {
+    let mut config = BackendConfig::read(&app);
+    config.source_policy = policy.clone();
+    config.save(&app)?;
+
+    let forwards = {
+        let mut s = state.lock().map_err(|e| e.to_string())?;
+        s.sources.policy = policy;
+        s.rearbitrate()
+    };
+    forward(&app, forwards);
     Ok(())
 }
 
@@ -271,6 +1001,10 @@ pub fn run() {
         auto_lock_delay: 3.0, // Default: 3 seconds
     }));
 
+    // Latest lyrics/progress, replayed to windows as they load
+    let now_playing: SharedNowPlaying = Arc::new(Mutex::new(NowPlaying::default()));
+    let page_load_now_playing = now_playing.clone();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_updater::Builder::new().build()) // Updater Init
@@ -278,6 +1012,21 @@ pub fn run() {
         .plugin(tauri_plugin_autostart::init(tauri_plugin_autostart::MacosLauncher::LaunchAgent, None))
         .plugin(tauri_plugin_deep_link::init()) // Deep Link / URL Scheme
         .manage(lock_state.clone()) // Manage properly in Tauri state
+        .manage(SharedServerStatus::default())
+        .manage(Arc::new(ServerControl::default()))
+        .manage(now_playing)
+        .manage(ws::push_channel())
+        .manage(events::event_bus())
+        .manage(SharedCommandQueue::default())
+        .manage(SharedProgressThrottle::default())
+        .manage(SharedPlaybackClock::default())
+        .manage(SharedPlaybackDetector::default())
+        .on_page_load(move |webview, payload| {
+            // Catch up reloaded or newly created windows with what is currently playing
+            if payload.event() == tauri::webview::PageLoadEvent::Finished {
+                now_playing::replay_to_webview(webview, &page_load_now_playing);
+            }
+        })
         .setup(move |app| {
             // Setup Tray Icon
             let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
@@ -285,7 +1034,10 @@ pub fn run() {
             let reset_pos_i = MenuItem::with_id(app, "reset_pos", "Reset Position", true, None::<&str>)?;
             let toggle_lock_i = MenuItem::with_id(app, "toggle_lock", "Lock/Unlock Toggle", true, None::<&str>)?;
             let devpanel_i = MenuItem::with_id(app, "devpanel", "Toggle DevTools", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&toggle_lock_i, &settings_i, &reset_pos_i, &devpanel_i, &quit_i])?;
+            let server_status_i = MenuItem::with_id(app, "server_status", "Server: Starting...", false, None::<&str>)?;
+            let restart_server_i = MenuItem::with_id(app, "restart_server", "Restart Server", true, None::<&str>)?;
+            let menu = Menu::with_items(app, &[&toggle_lock_i, &settings_i, &reset_pos_i, &server_status_i, &restart_server_i, &devpanel_i, &quit_i])?;
+            app.manage(TrayServerItem(server_status_i.clone()));
 
             // Get tray icon - use default_window_icon with proper error handling
             let tray_icon = app.default_window_icon()
@@ -308,38 +1060,20 @@ pub fn run() {
                     match event.id.as_ref() {
                         "quit" => app.exit(0),
                         "reset_pos" => {
+                             let _ = remote::move_to_preset(app, remote::PositionPreset::Default);
                              if let Some(window) = app.get_webview_window("main") {
-                                 let _ = window.set_position(PhysicalPosition::new(100, 100));
-                                 let _ = window.show();
                                  let _ = window.set_focus();
                              }
                         },
                         "settings" => {
-                            if let Some(settings_window) = app.get_webview_window("settings") {
-                                settings_window.show().unwrap();
-                                settings_window.set_focus().unwrap();
-                                let _ = settings_window.eval("window.location.replace('index.html?settings=true')");
-                            } else {
-                                let _ = tauri::WebviewWindowBuilder::new(
-                                    app,
-                                    "settings",
-                                    tauri::WebviewUrl::App("index.html?settings=true".into())
-                                )
-                                .title("Settings")
-                                .inner_size(400.0, 600.0)
-                                .resizable(true)
-                                .always_on_top(true)
-                                .build();
-                            }
+                            let _ = remote::open_settings(app);
+                        },
+                        "restart_server" => {
+                            app.state::<Arc<ServerControl>>().request_restart();
                         },
                         "toggle_lock" => {
-                             let state = app.state::<std::sync::Arc<std::sync::Mutex<AppLockState>>>();
-                             let mut lock_state = state.lock().unwrap();
-                             lock_state.is_locked = !lock_state.is_locked;
-                             let new_locked = lock_state.is_locked;
-                             
-                             // Emit event to frontend to update UI
-                             let _ = app.emit("lock-state-update", new_locked);
+                             // Emits lock-state-update so the frontend updates its UI
+                             let _ = remote::toggle_lock(app);
                         },
                         "devpanel" => {
                             #[cfg(debug_assertions)]
@@ -397,11 +1131,30 @@ pub fn run() {
             let app_handle = app.handle().clone();
             
             // Start HTTP server in background
+            let backend_config = BackendConfig::load(&app_handle);
+            if let Ok(mut now_playing) = app.state::<SharedNowPlaying>().lock() {
+                now_playing.sources.policy = backend_config.source_policy.clone();
+            }
+            app.manage::<SharedUnsyncedTiming>(Arc::new(Mutex::new(backend_config.unsynced_timing)));
+            app.manage::<SharedTranslationLanguages>(Arc::new(Mutex::new(
+                translations::normalize_languages(backend_config.translation_languages.clone()),
+            )));
+            app.manage::<SharedOriginPolicy>(Arc::new(OriginPolicy::new(backend_config.allowed_origins.clone())));
+            app.manage::<SharedTrackOffsets>(Arc::new(Mutex::new(TrackOffsets::load(&app_handle))));
+            app.manage::<SharedLatency>(Arc::new(Mutex::new(LatencyCompensator::new(backend_config.position_offset_ms))));
+            app.manage::<SharedAuth>(Arc::new(Mutex::new(AuthState {
+                token: auth::load_or_create_token(&app_handle),
+                required: backend_config.require_token,
+            })));
             let app_handle_http = app_handle.clone();
             tauri::async_runtime::spawn(async move {
-                start_http_server(app_handle_http).await;
+                server::supervise(app_handle_http).await;
             });
 
+            // Emit line-change events at lyric line boundaries
+            let clock = app.state::<SharedPlaybackClock>().inner().clone();
+            tauri::async_runtime::spawn(clock::drive(app_handle.clone(), clock));
+
             // Start Mouse Polling Thread
             let loop_lock_state = lock_state.clone();
             let loop_app_handle = app_handle.clone();
@@ -558,7 +1311,7 @@ pub fn run() {
                             // Trigger Unlock
                              if let Ok(mut state) = loop_lock_state.lock() {
                                  state.is_locked = false;
-                                 let _ = loop_app_handle.emit("lock-state-update", false);
+                                 emit_lock_state(&loop_app_handle, false);
                                  // Force reset ticks to avoid repeated toggling
                                  idle_ticks = 0;
                              }
@@ -621,7 +1374,7 @@ pub fn run() {
                             // Trigger auto-lock
                             if let Ok(mut state) = loop_lock_state.lock() {
                                 state.is_locked = true;
-                                let _ = loop_app_handle.emit("lock-state-update", true);
+                                emit_lock_state(&loop_app_handle, true);
                                 auto_lock_idle_ticks = 0;
                             }
                         }
@@ -661,6 +1414,31 @@ pub fn run() {
             set_hover_unlock_enabled,
             set_auto_lock_enabled,
             set_auto_lock_delay,
+            get_server_info,
+            get_server_status,
+            restart_server,
+            get_current_state,
+            request_resend,
+            enqueue_player_command,
+            get_progress_stats,
+            get_playback_clock,
+            get_latency_info,
+            set_position_offset,
+            save_position_offset,
+            get_track_offset,
+            nudge_track_offset,
+            reset_track_offset,
+            get_translation_languages,
+            get_unsynced_timing,
+            set_unsynced_timing,
+            set_translation_languages,
+            get_auth_info,
+            get_origin_stats,
+            regenerate_auth_token,
+            set_token_required,
+            get_sources,
+            get_source_policy,
+            set_source_policy,
             get_system_fonts
         ])
 
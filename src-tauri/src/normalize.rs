@@ -0,0 +1,376 @@
+use serde::Serialize;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::patch::LyricsPatch;
+use crate::{LyricLine, LyricsData};
+
+// One change made to a payload, returned in the lyrics ack
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizationFix {
+    pub line: Option<usize>, // Index in the payload as sent, None for fixes to the whole list
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl NormalizationFix {
+    fn new(line: Option<usize>, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+// Clean up lyrics as players send them (macOS decomposed Hangul, unsorted or duplicated
+// LRC lines, stray whitespace), before validation
+pub fn normalize(lyrics_data: &mut LyricsData, full_width_punctuation: bool) -> Vec<NormalizationFix> {
+    let mut fixes = Vec::new();
+
+    for (i, line) in lyrics_data.lyrics.iter_mut().enumerate() {
+        // Kept through sorting, merging and markers for reports and PATCH /lyrics indexes
+        line.payload_indexes = vec![i];
+
+        let mut changed = Vec::new();
+        for (name, text) in text_fields(line) {
+            let cleaned = clean_text(text, full_width_punctuation);
+            if *text != cleaned {
+                *text = cleaned;
+                changed.push(name);
+            }
+        }
+        if !changed.is_empty() {
+            fixes.push(NormalizationFix::new(
+                Some(i),
+                "text_normalized",
+                format!("Trimmed or normalized {}", changed.join(", ")),
+            ));
+        }
+        for segment in &mut line.segments {
+            segment.text = segment.text.nfc().collect();
+        }
+        // An emptied pronunciation or translation is the same as none
+        line.pron_text = line.pron_text.take().filter(|t| !t.is_empty());
+        line.trans_text = line.trans_text.take().filter(|t| !t.is_empty());
+        line.background_text = line.background_text.take().filter(|t| !t.is_empty());
+        line.translations.retain(|_, text| !text.is_empty());
+    }
+
+    if lyrics_data.is_synced {
+        let lines = &mut lyrics_data.lyrics;
+        remove_empty_lines(lines, &mut fixes);
+        if lines.windows(2).any(|pair| pair[1].start_time < pair[0].start_time) {
+            // Stable, lines sharing a timestamp keep their order for the merge below
+            lines.sort_by_key(|line| line.start_time);
+            fixes.push(NormalizationFix::new(None, "sorted", "Lines were not ordered by startTime"));
+        }
+        merge_duplicates(lines, &mut fixes);
+    }
+
+    fixes
+}
+
+// Same cleanup for the texts of PATCH /lyrics, an emptied text still removes the stored one
+pub fn normalize_patch(patch: &mut LyricsPatch, full_width_punctuation: bool) {
+    for line in &mut patch.lines {
+        let texts = line.trans_text.iter_mut().chain(line.pron_text.iter_mut()).chain(line.translations.values_mut());
+        for text in texts {
+            *text = clean_text(text, full_width_punctuation);
+        }
+    }
+}
+
+fn clean_text(text: &str, full_width_punctuation: bool) -> String {
+    let text = text.trim().nfc();
+    if full_width_punctuation {
+        text.map(half_width).collect()
+    } else {
+        text.collect()
+    }
+}
+
+// Every text of a line, segments only get NFC since their spacing separates words
+fn text_fields(line: &mut LyricLine) -> Vec<(&'static str, &mut String)> {
+    let mut fields = vec![("text", &mut line.text)];
+    fields.extend(line.pron_text.as_mut().map(|t| ("pronText", t)));
+    fields.extend(line.trans_text.as_mut().map(|t| ("transText", t)));
+    fields.extend(line.background_text.as_mut().map(|t| ("backgroundText", t)));
+    fields.extend(line.translations.values_mut().map(|t| ("translations", t)));
+    fields
+}
+
+// Full-width ASCII punctuation (！？，…) and the ideographic space, letters and digits stay
+fn half_width(c: char) -> char {
+    match c {
+        '\u{3000}' => ' ',
+        '\u{FF01}'..='\u{FF5E}' => {
+            let ascii = char::from_u32(c as u32 - 0xFEE0).unwrap_or(c);
+            if ascii.is_ascii_punctuation() {
+                ascii
+            } else {
+                c
+            }
+        }
+        _ => c,
+    }
+}
+
+// Blank lines carry nothing to show, they mark where the previous line ends.
+// Unsynced lyrics keep them as verse breaks for auto_timing.rs
+fn remove_empty_lines(lines: &mut Vec<LyricLine>, fixes: &mut Vec<NormalizationFix>) {
+    let mut kept: Vec<LyricLine> = Vec::with_capacity(lines.len());
+    for line in lines.drain(..) {
+        let is_empty = line.text.is_empty()
+            && line.background_text.is_none()
+            && line.pron_text.is_none()
+            && line.trans_text.is_none()
+            && line.translations.is_empty();
+        if !is_empty {
+            kept.push(line);
+            continue;
+        }
+        if let Some(previous) = kept.last_mut().filter(|p| p.end_time.is_none() && p.start_time <= line.start_time) {
+            previous.end_time = Some(line.start_time);
+        }
+        fixes.push(NormalizationFix::new(line.payload_index(), "empty_line_removed", "Removed empty line"));
+    }
+    *lines = kept;
+}
+
+// Lines sharing a timestamp: exact repeats are dropped, otherwise the second one is
+// taken as the translation (dual-language LRC) or appended to the text.
+// Duet lines (different singers) and lines both timed per word stay separate
+fn merge_duplicates(lines: &mut Vec<LyricLine>, fixes: &mut Vec<NormalizationFix>) {
+    let mut merged: Vec<LyricLine> = Vec::with_capacity(lines.len());
+    for line in lines.drain(..) {
+        let first = match merged.last_mut() {
+            Some(first) if first.start_time == line.start_time && can_merge(first, &line) => first,
+            _ => {
+                merged.push(line);
+                continue;
+            }
+        };
+
+        let i = line.payload_index();
+        if line.text == first.text {
+            fixes.push(NormalizationFix::new(i, "duplicate_removed", "Removed repeated line"));
+        } else if first.trans_text.is_none() && line.trans_text.is_none() {
+            first.trans_text = Some(line.text);
+            fixes.push(NormalizationFix::new(i, "merged_as_translation", "Line with the same startTime used as translation"));
+        } else {
+            first.text = format!("{} {}", first.text, line.text);
+            first.segments.extend(line.segments);
+            fixes.push(NormalizationFix::new(i, "merged", "Line with the same startTime appended to the previous one"));
+        }
+        // PATCH /lyrics addressing the dropped line lands on the one it was merged into
+        first.payload_indexes.extend(line.payload_indexes);
+        first.end_time = first.end_time.max(line.end_time);
+        first.trans_text = first.trans_text.take().or(line.trans_text);
+        first.pron_text = first.pron_text.take().or(line.pron_text);
+        first.background_text = first.background_text.take().or(line.background_text);
+        for (language, text) in line.translations {
+            first.translations.entry(language).or_insert(text);
+        }
+    }
+    *lines = merged;
+}
+
+fn can_merge(first: &LyricLine, line: &LyricLine) -> bool {
+    if first.singer != line.singer {
+        return false;
+    }
+    // Appending would put the second line's segments before the first's end
+    let would_append = line.text != first.text && (first.trans_text.is_some() || line.trans_text.is_some());
+    !(would_append && !first.segments.is_empty() && !line.segments.is_empty())
+}
+
+// Missing end times of synced lyrics become the next line's start, or the end of the track
+// for the last one. Runs after interludes.rs so unknown end times still open interlude markers,
+// a line before one then ends where the marker starts
+pub fn fill_end_times(lyrics_data: &mut LyricsData) -> Vec<NormalizationFix> {
+    let mut fixes = Vec::new();
+    if !lyrics_data.is_synced {
+        return fixes;
+    }
+    let duration = lyrics_data.track.duration as i64;
+    let lines = &mut lyrics_data.lyrics;
+    // Duet lines sharing a timestamp both run until the next later one
+    let next_starts: Vec<i64> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            lines[i + 1..].iter().map(|next| next.start_time).find(|&start| start > line.start_time).unwrap_or(duration)
+        })
+        .collect();
+    for (line, next_start) in lines.iter_mut().zip(next_starts) {
+        if line.end_time.is_none() && next_start >= line.start_time {
+            line.end_time = Some(next_start);
+            fixes.push(NormalizationFix::new(line.payload_index(), "end_time_filled", format!("endTime set to {}", next_start)));
+        }
+    }
+    fixes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn lyrics(is_synced: bool, lines: serde_json::Value) -> LyricsData {
+        serde_json::from_value(json!({
+            "track": { "title": "Song", "artist": "Artist", "album": "Album", "duration": 60000 },
+            "lyrics": lines,
+            "isSynced": is_synced,
+        }))
+        .unwrap()
+    }
+
+    fn codes(fixes: &[NormalizationFix]) -> Vec<&str> {
+        fixes.iter().map(|fix| fix.code).collect()
+    }
+
+    #[test]
+    fn sorts_by_start_time_keeping_payload_indexes() {
+        let mut data = lyrics(true, json!([
+            { "startTime": 2000, "endTime": 3000, "text": "b" },
+            { "startTime": 1000, "endTime": 2000, "text": "a" },
+        ]));
+        let fixes = normalize(&mut data, false);
+
+        assert_eq!(codes(&fixes), ["sorted"]);
+        let texts: Vec<&str> = data.lyrics.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(texts, ["a", "b"]);
+        assert_eq!(data.stored_index(0), Some(1));
+        assert_eq!(data.stored_index(1), Some(0));
+    }
+
+    #[test]
+    fn trims_and_composes_text() {
+        let mut data = lyrics(true, json!([
+            { "startTime": 0, "endTime": 1000, "text": " \u{1100}\u{1161} ", "transText": "" },
+        ]));
+        let fixes = normalize(&mut data, false);
+
+        assert_eq!(codes(&fixes), ["text_normalized"]);
+        assert_eq!(data.lyrics[0].text, "\u{AC00}");
+        assert_eq!(data.lyrics[0].trans_text, None);
+    }
+
+    #[test]
+    fn merges_lines_sharing_a_timestamp() {
+        let mut data = lyrics(true, json!([
+            { "startTime": 1000, "text": "hello" },
+            { "startTime": 1000, "text": "hello" },
+            { "startTime": 1000, "text": "안녕" },
+            { "startTime": 2000, "text": "world", "transText": "세상" },
+            { "startTime": 2000, "text": "again" },
+        ]));
+        let fixes = normalize(&mut data, false);
+
+        assert_eq!(codes(&fixes), ["duplicate_removed", "merged_as_translation", "merged"]);
+        assert_eq!(data.lyrics.len(), 2);
+        assert_eq!(data.lyrics[0].trans_text.as_deref(), Some("안녕"));
+        assert_eq!(data.lyrics[1].text, "world again");
+        assert_eq!(data.stored_index(2), Some(0));
+        assert_eq!(data.stored_index(4), Some(1));
+    }
+
+    #[test]
+    fn keeps_duet_lines_apart() {
+        let mut data = lyrics(true, json!([
+            { "startTime": 1000, "text": "first", "singer": "v1" },
+            { "startTime": 1000, "text": "second", "singer": "v2" },
+            { "startTime": 3000, "endTime": 4000, "text": "next" },
+        ]));
+        let fixes = normalize(&mut data, false);
+
+        assert!(fixes.is_empty());
+        assert_eq!(data.lyrics.len(), 3);
+        assert_eq!(data.lyrics[1].singer.as_deref(), Some("v2"));
+        assert_eq!(data.lyrics[1].trans_text, None);
+
+        fill_end_times(&mut data);
+        assert_eq!(data.lyrics[0].end_time, Some(3000));
+        assert_eq!(data.lyrics[1].end_time, Some(3000));
+    }
+
+    #[test]
+    fn keeps_word_timed_lines_apart() {
+        let mut data = lyrics(true, json!([
+            { "startTime": 1000, "text": "a b", "transText": "x", "segments": [
+                { "text": "a", "startTime": 1000, "endTime": 1500 },
+                { "text": "b", "startTime": 1500, "endTime": 2000 },
+            ] },
+            { "startTime": 1000, "text": "c", "segments": [
+                { "text": "c", "startTime": 1000, "endTime": 2000 },
+            ] },
+        ]));
+        normalize(&mut data, false);
+
+        assert_eq!(data.lyrics.len(), 2);
+        assert_eq!(data.lyrics[0].segments.len(), 2);
+        assert!(crate::validation::validate_lyrics(&data).is_empty());
+    }
+
+    #[test]
+    fn cleans_patch_texts() {
+        let mut patch: LyricsPatch = serde_json::from_value(json!({
+            "trackId": "song",
+            "lines": [{ "index": 0, "transText": " \u{1100}\u{1161}\u{FF01} ", "translations": { "ko": "  " } }],
+        }))
+        .unwrap();
+        normalize_patch(&mut patch, true);
+
+        assert_eq!(patch.lines[0].trans_text.as_deref(), Some("\u{AC00}!"));
+        assert_eq!(patch.lines[0].translations["ko"], "");
+    }
+
+    #[test]
+    fn drops_blank_lines_only_when_synced() {
+        let lines = json!([
+            { "startTime": 0, "text": "verse" },
+            { "startTime": 2000, "text": "" },
+            { "startTime": 4000, "text": "chorus" },
+        ]);
+        let mut synced = lyrics(true, lines.clone());
+        let fixes = normalize(&mut synced, false);
+        assert_eq!(codes(&fixes), ["empty_line_removed"]);
+        assert_eq!(synced.lyrics.len(), 2);
+        assert_eq!(synced.lyrics[0].end_time, Some(2000));
+
+        let mut unsynced = lyrics(false, lines);
+        assert!(normalize(&mut unsynced, false).is_empty());
+        assert_eq!(unsynced.lyrics.len(), 3);
+    }
+
+    #[test]
+    fn fills_end_times_up_to_the_next_line_or_the_track_end() {
+        let mut data = lyrics(true, json!([
+            { "startTime": 0, "text": "a" },
+            { "startTime": 2000, "endTime": 2500, "text": "b" },
+            { "startTime": 5000, "text": "c" },
+        ]));
+        normalize(&mut data, false);
+        let fixes = fill_end_times(&mut data);
+
+        assert_eq!(codes(&fixes), ["end_time_filled", "end_time_filled"]);
+        assert_eq!(fixes[1].line, Some(2));
+        let end_times: Vec<Option<i64>> = data.lyrics.iter().map(|line| line.end_time).collect();
+        assert_eq!(end_times, [Some(2000), Some(2500), Some(60000)]);
+    }
+
+    #[test]
+    fn leaves_interlude_gaps_open_before_filling_end_times() {
+        let mut data = lyrics(true, json!([
+            { "startTime": 0, "text": "a" },
+            { "startTime": 30000, "endTime": 60000, "text": "b" },
+        ]));
+        normalize(&mut data, false);
+        crate::interludes::insert_markers(&mut data, 8000);
+        fill_end_times(&mut data);
+
+        assert_eq!(data.lyrics[1].kind, crate::LineKind::Interlude);
+        assert_eq!(data.lyrics[0].end_time, Some(data.lyrics[1].start_time));
+    }
+}
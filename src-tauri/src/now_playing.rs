@@ -0,0 +1,163 @@
+use axum::http::StatusCode;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager, Runtime, Webview};
+
+use crate::api::ApiError;
+use crate::patch::{self, LyricsPatch, LyricsPatchEvent};
+use crate::sources::SourceRegistry;
+use crate::translations;
+use crate::{LyricsData, LyricsEvent, ProgressData, ProgressEvent};
+
+pub type SharedNowPlaying = Arc<Mutex<NowPlaying>>;
+
+// Latest data forwarded to the overlay, kept so late joiners can catch up
+#[derive(Debug, Default)]
+pub struct NowPlaying {
+    pub lyrics: Option<LyricsData>,
+    pub progress: Option<ProgressData>,
+    pub sources: SourceRegistry,
+}
+
+// Snapshot returned by GET /state and the get_current_state command
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSnapshot {
+    pub lyrics_data: Option<LyricsData>,
+    pub progress_data: Option<ProgressData>,
+    pub source: Option<String>, // Source whose data is shown
+}
+
+// What has to be emitted after the state changed
+#[derive(Debug, Clone)]
+pub enum Forward {
+    Lyrics(LyricsData),
+    Progress(ProgressData),
+    LyricsPatch(LyricsPatchEvent),
+    SourceChanged(String),
+    Cleared(ClearedEvent),
+}
+
+// Payload of `now-playing-cleared`: the new active source has not sent this data yet
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearedEvent {
+    pub lyrics: bool,
+    pub progress: bool,
+}
+
+impl NowPlaying {
+    pub fn snapshot(&self) -> StateSnapshot {
+        StateSnapshot {
+            lyrics_data: self.lyrics.clone(),
+            progress_data: self.progress.clone(),
+            source: self.sources.active().map(str::to_string),
+        }
+    }
+
+    pub fn receive_lyrics(&mut self, lyrics_data: LyricsData) -> Vec<Forward> {
+        let source = lyrics_data.source_id().to_string();
+        self.sources.record_lyrics(&source, lyrics_data.clone());
+
+        if self.sources.arbitrate() {
+            self.switch_to_active()
+        } else if self.sources.active() == Some(source.as_str()) {
+            self.lyrics = Some(lyrics_data.clone());
+            vec![Forward::Lyrics(lyrics_data)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub fn receive_progress(&mut self, progress_data: ProgressData) -> Vec<Forward> {
+        let source = progress_data.source_id().to_string();
+        self.sources.record_progress(&source, progress_data.clone());
+
+        if self.sources.arbitrate() {
+            self.switch_to_active()
+        } else if self.sources.active() == Some(source.as_str()) {
+            self.progress = Some(progress_data.clone());
+            vec![Forward::Progress(progress_data)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    // Patch the source's lyrics in place, forwarded only when that source is shown
+    pub fn patch_lyrics(&mut self, patch: &LyricsPatch, languages: &[String]) -> Result<Vec<Forward>, ApiError> {
+        let source = patch.source_id().to_string();
+        let lyrics_data = self.sources.lyrics_mut(&source).ok_or_else(|| {
+            ApiError::new(StatusCode::CONFLICT, "no_lyrics", format!("No lyrics received from source '{}'", source))
+        })?;
+        if lyrics_data.track_id() != patch.track_id {
+            return Err(ApiError::new(
+                StatusCode::CONFLICT,
+                "track_mismatch",
+                format!("Current track is '{}'", lyrics_data.track_id()),
+            ));
+        }
+
+        let event = patch::apply(lyrics_data, patch, languages).map_err(ApiError::validation)?;
+        let patched = lyrics_data.clone();
+
+        if self.sources.active() == Some(source.as_str()) {
+            self.lyrics = Some(patched);
+            Ok(vec![Forward::LyricsPatch(event)])
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    // Re-run arbitration after the policy changed
+    pub fn rearbitrate(&mut self) -> Vec<Forward> {
+        if self.sources.arbitrate() {
+            self.switch_to_active()
+        } else {
+            Vec::new()
+        }
+    }
+
+    // Replace the forwarded data with the new active source's latest lyrics/progress
+    fn switch_to_active(&mut self) -> Vec<Forward> {
+        let (id, source) = match (self.sources.active(), self.sources.active_source()) {
+            (Some(id), Some(source)) => (id.to_string(), source),
+            _ => return Vec::new(),
+        };
+
+        let mut forwards = vec![Forward::SourceChanged(id)];
+        // Never show the previous source's data against the new one's
+        let cleared = ClearedEvent {
+            lyrics: source.lyrics.is_none() && self.lyrics.take().is_some(),
+            progress: source.progress.is_none() && self.progress.take().is_some(),
+        };
+        if let Some(lyrics_data) = source.lyrics.clone() {
+            self.lyrics = Some(lyrics_data.clone());
+            forwards.push(Forward::Lyrics(lyrics_data));
+        }
+        if let Some(progress_data) = source.progress.clone() {
+            self.progress = Some(progress_data.clone());
+            forwards.push(Forward::Progress(progress_data));
+        }
+        if cleared.lyrics || cleared.progress {
+            forwards.push(Forward::Cleared(cleared));
+        }
+        forwards
+    }
+}
+
+// Re-emit the current state to a single (re)loaded webview
+pub fn replay_to_webview<R: Runtime>(webview: &Webview<R>, now_playing: &SharedNowPlaying) {
+    let snapshot = match now_playing.lock() {
+        Ok(s) => s.snapshot(),
+        Err(_) => return,
+    };
+
+    let label = webview.label().to_string();
+    if let Some(lyrics_data) = snapshot.lyrics_data {
+        let lyrics_data = translations::lyrics_for_overlay(webview.app_handle(), &lyrics_data);
+        let _ = webview.emit_to(label.as_str(), "lyrics-update", LyricsEvent { lyrics_data });
+    }
+    if let Some(progress_data) = snapshot.progress_data {
+        let _ = webview.emit_to(label.as_str(), "progress-update", ProgressEvent { progress_data });
+    }
+}
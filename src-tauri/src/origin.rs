@@ -0,0 +1,161 @@
+use axum::extract::Request;
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use tauri::{Emitter, Runtime};
+use tower_http::cors::AllowOrigin;
+
+use crate::api::ApiError;
+use crate::AppState;
+
+pub type SharedOriginPolicy = Arc<OriginPolicy>;
+
+// Browser origins allowed to talk to the ingest server, managed once so the
+// rejected counter survives server restarts
+pub struct OriginPolicy {
+    allowed: RwLock<Vec<String>>,
+    bind_host: RwLock<String>, // `host` from backend.json, accepted in the Host header besides localhost and IPs
+    rejected: AtomicU64,
+    last_rejected: Mutex<Option<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OriginRejectedEvent {
+    pub origin: String,
+    pub total_rejected: u64,
+}
+
+// Returned by the get_origin_stats command, for a settings window opened after the events
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OriginStats {
+    pub total_rejected: u64,
+    pub last_origin: Option<String>,
+}
+
+impl OriginPolicy {
+    pub fn new(allowed: Vec<String>) -> Self {
+        Self {
+            allowed: RwLock::new(normalize(allowed)),
+            bind_host: RwLock::new(String::new()),
+            rejected: AtomicU64::new(0),
+            last_rejected: Mutex::new(None),
+        }
+    }
+
+    pub fn stats(&self) -> OriginStats {
+        OriginStats {
+            total_rejected: self.rejected.load(Ordering::Relaxed),
+            last_origin: self.last_rejected.lock().ok().and_then(|last| last.clone()),
+        }
+    }
+
+    // Count a rejected request, returns the new total
+    fn record_rejected(&self, origin: &str) -> u64 {
+        if let Ok(mut last) = self.last_rejected.lock() {
+            *last = Some(origin.to_string());
+        }
+        self.rejected.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    // Called by run_server with the allowlist from the current config
+    pub fn set_allowed(&self, allowed: Vec<String>) {
+        if let Ok(mut a) = self.allowed.write() {
+            *a = normalize(allowed);
+        }
+    }
+
+    // Called by run_server with the host the server binds to
+    pub fn set_bind_host(&self, host: &str) {
+        if let Ok(mut h) = self.bind_host.write() {
+            *h = host.to_string();
+        }
+    }
+
+    // DNS rebinding points an attacker's name at 127.0.0.1, the browser then sends that name as Host
+    pub fn is_allowed_host(&self, host: &str) -> bool {
+        let name = match host.strip_prefix('[') {
+            Some(rest) => rest.split(']').next().unwrap_or_default(),
+            None => host.rsplit_once(':').map(|(name, _)| name).unwrap_or(host),
+        };
+        name.eq_ignore_ascii_case("localhost")
+            || name.parse::<std::net::IpAddr>().is_ok()
+            || self.bind_host.read().is_ok_and(|h| name.eq_ignore_ascii_case(&h))
+    }
+
+    // Entries without a port also match the same origin on any port (e.g. `http://localhost`)
+    pub fn is_allowed(&self, origin: &str) -> bool {
+        let allowed = match self.allowed.read() {
+            Ok(allowed) => allowed,
+            Err(_) => return false,
+        };
+        allowed.iter().any(|allowed| {
+            origin == allowed
+                || origin
+                    .strip_prefix(allowed.as_str())
+                    .and_then(|rest| rest.strip_prefix(':'))
+                    .is_some_and(|port| !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()))
+        })
+    }
+
+    pub fn cors_origin(policy: Arc<Self>) -> AllowOrigin {
+        AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+            origin.to_str().map(|o| policy.is_allowed(o)).unwrap_or(false)
+        })
+    }
+}
+
+fn normalize(allowed: Vec<String>) -> Vec<String> {
+    allowed.into_iter().map(|o| o.trim_end_matches('/').to_string()).collect()
+}
+
+// Outermost middleware: reject requests (including preflights) from origins not on the allowlist
+// and with a Host that isn't ours. Requests without an Origin header come from non-browser clients
+// or no-cors GETs, they are left to the auth check (see auth.rs for the command routes).
+pub async fn reject_unknown_origins<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let host = req.headers().get(header::HOST).map(|v| v.to_str().unwrap_or_default().to_string());
+    if let Some(host) = host.filter(|h| !state.origins.is_allowed_host(h)) {
+        let total_rejected = state.origins.record_rejected(&host);
+        eprintln!("Rejected request for host {}", host);
+        let _ = state.app_handle.emit(
+            "origin-rejected",
+            OriginRejectedEvent { origin: host.clone(), total_rejected },
+        );
+
+        return ApiError::new(StatusCode::FORBIDDEN, "host_not_allowed", format!("Host {} is not allowed", host))
+            .into_response();
+    }
+
+    let origin = req
+        .headers()
+        .get(header::ORIGIN)
+        .map(|v| v.to_str().unwrap_or_default().to_string());
+
+    if let Some(origin) = origin {
+        if !state.origins.is_allowed(&origin) {
+            let total_rejected = state.origins.record_rejected(&origin);
+            eprintln!("Rejected request from origin {}", origin);
+            let _ = state.app_handle.emit(
+                "origin-rejected",
+                OriginRejectedEvent { origin: origin.clone(), total_rejected },
+            );
+
+            return ApiError::new(
+                StatusCode::FORBIDDEN,
+                "origin_not_allowed",
+                format!("Origin {} is not allowed", origin),
+            )
+            .into_response();
+        }
+    }
+
+    next.run(req).await
+}
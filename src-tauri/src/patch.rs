@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::sources;
+use crate::translations;
+use crate::validation::FieldError;
+use crate::{LineKind, LyricLine, LyricsData};
+
+// PATCH /lyrics payload: late translations/pronunciations for the lyrics already sent
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsPatch {
+    pub track_id: String, // Current track's `id`, or its title when the lyrics were sent without one
+    pub lines: Vec<LinePatch>,
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+impl LyricsPatch {
+    pub fn source_id(&self) -> &str {
+        self.source.as_deref().unwrap_or(sources::DEFAULT_SOURCE_ID)
+    }
+}
+
+// A line is addressed by `index` or by `startTime`
+// Absent texts are left untouched, an empty string removes the text
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinePatch {
+    #[serde(default)]
+    pub index: Option<usize>,
+    #[serde(default)]
+    pub start_time: Option<i64>,
+    #[serde(default)]
+    pub trans_text: Option<String>,
+    #[serde(default)]
+    pub pron_text: Option<String>,
+    #[serde(default)]
+    pub translations: BTreeMap<String, String>, // Per-language, merged into the line's translations
+}
+
+// Payload of the `lyrics-patch` event, lines are resolved to indexes
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LyricsPatchEvent {
+    pub track_id: String,
+    pub lines: Vec<PatchedLine>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchedLine {
+    pub index: usize,
+    pub trans_text: Option<String>,
+    pub pron_text: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, String>, // All translations of the line after the patch
+}
+
+// Stored lyric lines with their indexes, interlude markers skipped
+fn lyric_lines(lyrics_data: &LyricsData) -> impl Iterator<Item = (usize, &LyricLine)> {
+    lyrics_data.lyrics.iter().enumerate().filter(|(_, line)| line.kind == LineKind::Lyric)
+}
+
+// Resolve every line before touching anything so a bad patch changes nothing
+// `languages` is the overlay selection, used to pick the `transText` alias
+pub fn apply(
+    lyrics_data: &mut LyricsData,
+    patch: &LyricsPatch,
+    languages: &[String],
+) -> Result<LyricsPatchEvent, Vec<FieldError>> {
+    let mut errors = Vec::new();
+    let mut indexes = Vec::with_capacity(patch.lines.len());
+
+    for (i, line) in patch.lines.iter().enumerate() {
+        let found = match (line.index, line.start_time) {
+            // Indexes count the lines the player sent, before normalization and interlude markers
+            (Some(index), _) => lyrics_data.stored_index(index),
+            (None, Some(start_time)) => lyric_lines(lyrics_data).find(|(_, l)| l.start_time == start_time).map(|(i, _)| i),
+            (None, None) => None,
+        };
+        match found {
+            Some(index) => indexes.push(index),
+            None => errors.push(FieldError::new(
+                format!("lines[{}]", i),
+                "line_not_found",
+                "No lyric line matches this index or startTime",
+            )),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut lines = Vec::with_capacity(indexes.len());
+    for (index, line) in indexes.into_iter().zip(&patch.lines) {
+        let target = &mut lyrics_data.lyrics[index];
+        if let Some(trans_text) = &line.trans_text {
+            target.trans_text = Some(trans_text.clone()).filter(|t| !t.is_empty());
+        }
+        if let Some(pron_text) = &line.pron_text {
+            target.pron_text = Some(pron_text.clone()).filter(|t| !t.is_empty());
+        }
+        for (language, text) in &line.translations {
+            let previous = if text.is_empty() {
+                target.translations.remove(language)
+            } else {
+                target.translations.insert(language.clone(), text.clone())
+            };
+            // An alias of the replaced translation follows it, unless transText was patched too
+            if line.trans_text.is_none() && previous.is_some() && target.trans_text == previous {
+                target.trans_text = Some(text.clone()).filter(|t| !t.is_empty());
+            }
+        }
+        translations::fill_alias(target, languages);
+        lines.push(PatchedLine {
+            index,
+            trans_text: target.trans_text.clone(),
+            pron_text: target.pron_text.clone(),
+            translations: target.translations.clone(),
+        });
+    }
+
+    Ok(LyricsPatchEvent { track_id: patch.track_id.clone(), lines })
+}
@@ -0,0 +1,204 @@
+use axum::extract::rejection::JsonRejection;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, Runtime};
+
+use crate::api::{Ack, ApiError};
+use crate::validation::FieldError;
+use crate::{emit_lock_state, AppLockState, AppState};
+
+// Overlay actions shared by the tray menu, Tauri commands and the /control routes
+
+// Distance from the screen edge for the corner/edge presets
+const PRESET_MARGIN: i32 = 48;
+
+// Where `reset_pos` has always put the overlay
+const DEFAULT_POSITION: (i32, i32) = (100, 100);
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PositionPreset {
+    Default,
+    TopLeft,
+    TopCenter,
+    TopRight,
+    Center,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+pub fn set_locked<R: Runtime>(app: &AppHandle<R>, locked: bool) -> Result<(), String> {
+    let state = app.state::<Arc<Mutex<AppLockState>>>();
+    state.lock().map_err(|e| e.to_string())?.is_locked = locked;
+    emit_lock_state(app, locked);
+    Ok(())
+}
+
+pub fn toggle_lock<R: Runtime>(app: &AppHandle<R>) -> Result<bool, String> {
+    let state = app.state::<Arc<Mutex<AppLockState>>>();
+    let locked = {
+        let mut lock_state = state.lock().map_err(|e| e.to_string())?;
+        lock_state.is_locked = !lock_state.is_locked;
+        lock_state.is_locked
+    };
+    emit_lock_state(app, locked);
+    Ok(locked)
+}
+
+pub fn set_visible<R: Runtime>(app: &AppHandle<R>, visible: bool) -> Result<(), String> {
+    let window = app.get_webview_window("main").ok_or("Overlay window not found")?;
+    if visible {
+        window.show().map_err(|e| e.to_string())
+    } else {
+        window.hide().map_err(|e| e.to_string())
+    }
+}
+
+pub fn move_to_preset<R: Runtime>(app: &AppHandle<R>, preset: PositionPreset) -> Result<(), String> {
+    let window = app.get_webview_window("main").ok_or("Overlay window not found")?;
+
+    let (x, y) = match preset {
+        PositionPreset::Default => DEFAULT_POSITION,
+        _ => {
+            let monitor = match window.current_monitor().map_err(|e| e.to_string())? {
+                Some(monitor) => monitor,
+                None => window.primary_monitor().map_err(|e| e.to_string())?.ok_or("No monitor found")?,
+            };
+            let size = window.outer_size().map_err(|e| e.to_string())?;
+            let (origin, screen) = (monitor.position(), monitor.size());
+            let free_x = screen.width as i32 - size.width as i32;
+            let free_y = screen.height as i32 - size.height as i32;
+
+            let x = match preset {
+                PositionPreset::TopLeft | PositionPreset::BottomLeft => PRESET_MARGIN,
+                PositionPreset::TopRight | PositionPreset::BottomRight => free_x - PRESET_MARGIN,
+                _ => free_x / 2,
+            };
+            let y = match preset {
+                PositionPreset::TopLeft | PositionPreset::TopCenter | PositionPreset::TopRight => PRESET_MARGIN,
+                PositionPreset::Center => free_y / 2,
+                _ => free_y - PRESET_MARGIN,
+            };
+            (origin.x + x, origin.y + y)
+        }
+    };
+
+    window.set_position(PhysicalPosition::new(x, y)).map_err(|e| e.to_string())?;
+    window.show().map_err(|e| e.to_string())
+}
+
+pub fn open_settings<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    // Check if settings window already exists
+    if let Some(settings_window) = app.get_webview_window("settings") {
+        settings_window.show().map_err(|e| e.to_string())?;
+        settings_window.set_focus().map_err(|e| e.to_string())?;
+        // Force reload to ensure correct query param
+        settings_window.eval("window.location.replace('index.html?settings=true')").map_err(|e| e.to_string())?;
+    } else {
+        // Create new settings window
+        let _settings_window = tauri::WebviewWindowBuilder::new(
+            app,
+            "settings",
+            tauri::WebviewUrl::App("index.html?settings=true".into())
+        )
+        .title("Settings")
+        .inner_size(400.0, 600.0)
+        .resizable(true)
+        .decorations(true)
+        .always_on_top(true)
+        .build()
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// Profiles are settings snapshots kept by the frontend, the overlay window applies them
+pub fn switch_profile<R: Runtime>(app: &AppHandle<R>, name: &str) -> Result<(), String> {
+    app.emit("profile-switch", ProfileSwitchEvent { name: name.to_string() })
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSwitchEvent {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionRequest {
+    preset: PositionPreset,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileRequest {
+    name: String,
+}
+
+fn done(action: &'static str, result: Result<(), String>) -> Result<Json<Ack>, ApiError> {
+    result.map_err(ApiError::internal)?;
+    Ok(Json(Ack::Control { action, locked: None }))
+}
+
+// POST /control/* handlers, always behind the token (see auth::require_token)
+pub async fn handle_show<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+) -> Result<Json<Ack>, ApiError> {
+    done("show", set_visible(&state.app_handle, true))
+}
+
+pub async fn handle_hide<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+) -> Result<Json<Ack>, ApiError> {
+    done("hide", set_visible(&state.app_handle, false))
+}
+
+pub async fn handle_lock<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+) -> Result<Json<Ack>, ApiError> {
+    set_locked(&state.app_handle, true).map_err(ApiError::internal)?;
+    Ok(Json(Ack::Control { action: "lock", locked: Some(true) }))
+}
+
+pub async fn handle_unlock<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+) -> Result<Json<Ack>, ApiError> {
+    set_locked(&state.app_handle, false).map_err(ApiError::internal)?;
+    Ok(Json(Ack::Control { action: "unlock", locked: Some(false) }))
+}
+
+pub async fn handle_toggle_lock<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+) -> Result<Json<Ack>, ApiError> {
+    let locked = toggle_lock(&state.app_handle).map_err(ApiError::internal)?;
+    Ok(Json(Ack::Control { action: "toggleLock", locked: Some(locked) }))
+}
+
+pub async fn handle_position<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+    payload: Result<Json<PositionRequest>, JsonRejection>,
+) -> Result<Json<Ack>, ApiError> {
+    let Json(request) = payload?;
+    done("position", move_to_preset(&state.app_handle, request.preset))
+}
+
+pub async fn handle_settings<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+) -> Result<Json<Ack>, ApiError> {
+    done("settings", open_settings(&state.app_handle))
+}
+
+pub async fn handle_profile<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+    payload: Result<Json<ProfileRequest>, JsonRejection>,
+) -> Result<Json<Ack>, ApiError> {
+    let Json(request) = payload?;
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err(ApiError::validation(vec![FieldError::new("name", "empty", "Profile name must not be empty")]));
+    }
+    done("profile", switch_profile(&state.app_handle, name))
+}
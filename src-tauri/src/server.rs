@@ -0,0 +1,131 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::menu::MenuItem;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::Notify;
+
+use crate::config::{BackendConfig, ServerInfo};
+
+// Retry delays after the server failed to bind or crashed
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+pub type SharedServerStatus = Arc<Mutex<ServerStatus>>;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum ServerStatus {
+    #[default]
+    Starting,
+    Listening(ServerInfo),
+    #[serde(rename_all = "camelCase")]
+    Error { message: String, retry_in_ms: u64 },
+}
+
+impl ServerStatus {
+    pub fn info(&self) -> Option<ServerInfo> {
+        match self {
+            ServerStatus::Listening(info) => Some(info.clone()),
+            _ => None,
+        }
+    }
+
+    fn tray_text(&self) -> String {
+        match self {
+            ServerStatus::Starting => "Server: Starting...".to_string(),
+            ServerStatus::Listening(info) => match (&info.host, info.port) {
+                (Some(host), Some(port)) => format!("Server: {}:{}", host, port),
+                _ => "Server: Local IPC".to_string(),
+            },
+            ServerStatus::Error { retry_in_ms, .. } => {
+                format!("Server: Error (retry in {}s)", retry_in_ms.div_ceil(1000))
+            }
+        }
+    }
+}
+
+// Payload of the `server-error` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerErrorEvent {
+    pub message: String,
+    pub retry_in_ms: u64,
+}
+
+// Lets the tray / commands restart the server without relaunching the app
+#[derive(Default)]
+pub struct ServerControl {
+    restart: Notify,
+}
+
+impl ServerControl {
+    pub fn request_restart(&self) {
+        // notify_one keeps the permit if the server isn't waiting yet
+        self.restart.notify_one();
+    }
+
+    pub async fn restart_requested(&self) {
+        self.restart.notified().await;
+    }
+}
+
+// Tray entry showing the server state, managed so the supervisor can update it
+pub struct TrayServerItem<R: Runtime>(pub MenuItem<R>);
+
+pub fn set_status<R: Runtime>(app: &AppHandle<R>, status: ServerStatus) {
+    if let Some(item) = app.try_state::<TrayServerItem<R>>() {
+        let _ = item.0.set_text(status.tray_text());
+    }
+    let _ = app.emit("server-status", status.clone());
+    if let Ok(mut s) = app.state::<SharedServerStatus>().lock() {
+        *s = status;
+    }
+}
+
+// Run the server forever: retry with backoff on failure, restart on request
+pub async fn supervise<R: Runtime>(app: AppHandle<R>) {
+    let control = app.state::<Arc<ServerControl>>().inner().clone();
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+        // Re-read the config so a restart picks up edits to backend.json
+        let config = BackendConfig::load(&app);
+        set_status(&app, ServerStatus::Starting);
+
+        let started = Instant::now();
+        let shutdown_control = control.clone();
+        let result = crate::run_server(app.clone(), config, async move {
+            shutdown_control.restart_requested().await;
+        })
+        .await;
+
+        let message = match result {
+            Ok(()) => {
+                println!("HTTP server restarting");
+                backoff = INITIAL_BACKOFF;
+                continue;
+            }
+            Err(message) => message,
+        };
+
+        // A server that ran for a while before failing starts over with a short delay
+        if started.elapsed() > MAX_BACKOFF {
+            backoff = INITIAL_BACKOFF;
+        }
+
+        eprintln!("HTTP server error: {} (retrying in {:?})", message, backoff);
+        let retry_in_ms = backoff.as_millis() as u64;
+        let _ = app.emit("server-error", ServerErrorEvent { message: message.clone(), retry_in_ms });
+        set_status(&app, ServerStatus::Error { message, retry_in_ms });
+
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+            _ = control.restart_requested() => {
+                backoff = INITIAL_BACKOFF;
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::{LyricsData, ProgressData};
+
+// Source id used when the payload doesn't carry one
+pub const DEFAULT_SOURCE_ID: &str = "default";
+
+// A source that hasn't sent anything for this long no longer counts as playing
+const STALE_AFTER: Duration = Duration::from_secs(30);
+
+// Which source's data reaches the overlay when several players post at once
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum ArbitrationPolicy {
+    #[default]
+    MostRecentlyPlaying,
+    FixedPriority { order: Vec<String> }, // First playing source in `order` wins, unknown ids rank last
+    Manual { source: String },
+}
+
+#[derive(Debug)]
+pub struct Source {
+    pub lyrics: Option<LyricsData>,
+    pub progress: Option<ProgressData>,
+    last_activity: Instant,
+    last_playing: Option<Instant>, // Last progress update with is_playing set
+}
+
+impl Source {
+    fn new() -> Self {
+        Self {
+            lyrics: None,
+            progress: None,
+            last_activity: Instant::now(),
+            last_playing: None,
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.progress.as_ref().is_some_and(|p| p.is_playing) && self.last_activity.elapsed() < STALE_AFTER
+    }
+}
+
+// Per-source summary for the settings window
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceInfo {
+    pub id: String,
+    pub is_playing: bool,
+    pub idle_ms: u64, // Time since the source last sent anything
+    pub track: Option<String>,
+    pub active: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct SourceRegistry {
+    sources: HashMap<String, Source>,
+    active: Option<String>,
+    pub policy: ArbitrationPolicy,
+}
+
+impl SourceRegistry {
+    pub fn active(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+
+    pub fn active_source(&self) -> Option<&Source> {
+        self.active.as_ref().and_then(|id| self.sources.get(id))
+    }
+
+    fn touch(&mut self, id: &str) -> &mut Source {
+        let source = self.sources.entry(id.to_string()).or_insert_with(Source::new);
+        source.last_activity = Instant::now();
+        source
+    }
+
+    pub fn record_lyrics(&mut self, id: &str, lyrics_data: LyricsData) {
+        self.touch(id).lyrics = Some(lyrics_data);
+    }
+
+    pub fn lyrics_mut(&mut self, id: &str) -> Option<&mut LyricsData> {
+        self.sources.get_mut(id).and_then(|s| s.lyrics.as_mut())
+    }
+
+    pub fn record_progress(&mut self, id: &str, progress_data: ProgressData) {
+        let source = self.touch(id);
+        if progress_data.is_playing {
+            source.last_playing = Some(Instant::now());
+        }
+        source.progress = Some(progress_data);
+    }
+
+    // Re-run the policy, returns true when the active source changed
+    pub fn arbitrate(&mut self) -> bool {
+        let selected = self.select();
+        if selected.is_some() && selected != self.active {
+            self.active = selected;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn select(&self) -> Option<String> {
+        match &self.policy {
+            ArbitrationPolicy::Manual { source } => {
+                if self.sources.contains_key(source) {
+                    Some(source.clone())
+                } else {
+                    // Picked source hasn't shown up yet -> keep what we have
+                    self.active.clone()
+                }
+            }
+            ArbitrationPolicy::FixedPriority { order } => {
+                let rank = |id: &str| order.iter().position(|o| o == id).unwrap_or(order.len());
+                // Playing sources first, then by priority, then most recent activity
+                self.sources
+                    .iter()
+                    .min_by_key(|(id, s)| (!s.is_playing(), rank(id), Reverse(s.last_activity)))
+                    .map(|(id, _)| id.clone())
+            }
+            ArbitrationPolicy::MostRecentlyPlaying => self
+                .sources
+                .iter()
+                .filter(|(_, s)| s.is_playing())
+                .max_by_key(|(_, s)| s.last_playing)
+                .map(|(id, _)| id.clone())
+                // Nothing playing -> stick with the current source, or take the latest one
+                .or_else(|| self.active.clone())
+                .or_else(|| {
+                    self.sources
+                        .iter()
+                        .max_by_key(|(_, s)| s.last_activity)
+                        .map(|(id, _)| id.clone())
+                }),
+        }
+    }
+
+    pub fn infos(&self) -> Vec<SourceInfo> {
+        let mut infos: Vec<SourceInfo> = self
+            .sources
+            .iter()
+            .map(|(id, source)| SourceInfo {
+                id: id.clone(),
+                is_playing: source.is_playing(),
+                idle_ms: source.last_activity.elapsed().as_millis() as u64,
+                track: source.lyrics.as_ref().map(|l| l.track.title.clone()),
+                active: self.active.as_deref() == Some(id.as_str()),
+            })
+            .collect();
+        infos.sort_by(|a, b| a.id.cmp(&b.id));
+        infos
+    }
+}
@@ -0,0 +1,126 @@
+use axum::extract::Query;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::{Manager, Runtime};
+
+use crate::AppState;
+
+// Bumped on breaking changes to the payloads; older versions keep working under /v1
+pub const PROTOCOL_VERSION: u32 = 1;
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[u32] = &[1];
+
+// Routes served both at the root (legacy) and under /v1
+pub const ROUTES: &[RouteInfo] = &[
+    RouteInfo { method: "GET", path: "/status" },
+    RouteInfo { method: "POST", path: "/lyrics" },
+    RouteInfo { method: "PATCH", path: "/lyrics" },
+    RouteInfo { method: "POST", path: "/progress" },
+    RouteInfo { method: "GET", path: "/state" },
+    RouteInfo { method: "GET", path: "/ws" },
+    RouteInfo { method: "GET", path: "/events" },
+    RouteInfo { method: "GET", path: "/commands" },
+    RouteInfo { method: "POST", path: "/commands/:id/ack" },
+    RouteInfo { method: "POST", path: "/control/show" },
+    RouteInfo { method: "POST", path: "/control/hide" },
+    RouteInfo { method: "POST", path: "/control/lock" },
+    RouteInfo { method: "POST", path: "/control/unlock" },
+    RouteInfo { method: "POST", path: "/control/toggle-lock" },
+    RouteInfo { method: "POST", path: "/control/position" },
+    RouteInfo { method: "POST", path: "/control/settings" },
+    RouteInfo { method: "POST", path: "/control/profile" },
+];
+
+// Optional payload fields this version understands
+const LYRICS_OPTIONAL_FIELDS: &[&str] = &[
+    "track.id",
+    "track.albumArt",
+    "lyrics[].endTime",
+    "lyrics[].pronText",
+    "lyrics[].transText",
+    "lyrics[].translations",
+    "lyrics[].segments",
+    "lyrics[].segments[].singer",
+    "lyrics[].singer",
+    "lyrics[].backgroundText",
+    "lyrics[].kind",
+    "source",
+];
+const PROGRESS_OPTIONAL_FIELDS: &[&str] = &["duration", "remaining", "nextTrack", "source", "sentAt"];
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct RouteInfo {
+    pub method: &'static str,
+    pub path: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionalFields {
+    pub lyrics: &'static [&'static str],
+    pub progress: &'static [&'static str],
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusResponse {
+    pub status: &'static str,
+    pub app: String,
+    pub version: String,
+    pub protocol_version: u32,
+    pub supported_protocol_versions: &'static [u32],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compatible: Option<bool>, // Only when the client announced its protocol version
+    pub auth_required: bool,
+    pub routes: &'static [RouteInfo],
+    pub optional_fields: OptionalFields,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusQuery {
+    protocol_version: Option<u32>,
+}
+
+// GET /status: version/health handshake, reachable without a token
+pub async fn handle_status<R: Runtime>(
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+    Query(query): Query<StatusQuery>,
+) -> Json<StatusResponse> {
+    let package_info = state.app_handle.package_info();
+    let auth_required = state.auth.lock().map(|auth| auth.required).unwrap_or(true);
+
+    Json(StatusResponse {
+        status: "ok",
+        app: package_info.name.clone(),
+        version: package_info.version.to_string(),
+        protocol_version: PROTOCOL_VERSION,
+        supported_protocol_versions: SUPPORTED_PROTOCOL_VERSIONS,
+        compatible: query
+            .protocol_version
+            .map(|version| SUPPORTED_PROTOCOL_VERSIONS.contains(&version)),
+        auth_required,
+        routes: ROUTES,
+        optional_fields: OptionalFields {
+            lyrics: LYRICS_OPTIONAL_FIELDS,
+            progress: PROGRESS_OPTIONAL_FIELDS,
+        },
+    })
+}
+
+// Paths that skip the token check
+pub fn is_public_path(path: &str) -> bool {
+    path == "/status" || path == "/v1/status"
+}
+
+// Player command routes, see commands.rs
+pub fn is_command_path(path: &str) -> bool {
+    let path = path.strip_prefix("/v1").unwrap_or(path);
+    path == "/commands" || path.starts_with("/commands/")
+}
+
+// Remote control routes, see remote.rs
+pub fn is_control_path(path: &str) -> bool {
+    let path = path.strip_prefix("/v1").unwrap_or(path);
+    path.starts_with("/control/")
+}
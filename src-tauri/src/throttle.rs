@@ -0,0 +1,144 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::detect::{expected_position, SEEK_THRESHOLD_MS};
+use crate::{ProgressData, ProgressEvent};
+
+pub type SharedProgressThrottle = Arc<Mutex<ProgressThrottle>>;
+
+// Counters shown in the settings window
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThrottleStats {
+    pub received: u64,
+    pub emitted: u64,
+    pub immediate: u64, // Emitted right away because of a play/pause change or a seek
+    pub coalesced: u64, // Replaced by a newer update before being emitted
+}
+
+// Limits how often progress-update reaches the overlay webview
+#[derive(Debug, Default)]
+pub struct ProgressThrottle {
+    min_interval: Duration, // Zero disables throttling
+    last_emitted: Option<(Instant, ProgressData)>,
+    pending: Option<ProgressData>,
+    flush_scheduled: bool,
+    stats: ThrottleStats,
+}
+
+enum Decision {
+    Emit,
+    Hold,
+    Schedule(Duration), // Hold and flush after this delay
+}
+
+impl ProgressThrottle {
+    // `max_rate` is in emits per second, 0 means unlimited
+    pub fn set_max_rate(&mut self, max_rate: f64) {
+        self.min_interval = if max_rate > 0.0 {
+            Duration::from_secs_f64(1.0 / max_rate)
+        } else {
+            Duration::ZERO
+        };
+    }
+
+    pub fn stats(&self) -> ThrottleStats {
+        self.stats
+    }
+
+    // Forget the last emit so the next update goes through (e.g. after a source switch)
+    pub fn reset(&mut self) {
+        self.last_emitted = None;
+    }
+
+    fn offer(&mut self, progress_data: ProgressData) -> Decision {
+        self.stats.received += 1;
+
+        let (since_last, transition) = match &self.last_emitted {
+            None => return self.emit_now(progress_data, false),
+            Some((at, last)) => (at.elapsed(), is_transition(last, at.elapsed(), &progress_data)),
+        };
+
+        if transition {
+            return self.emit_now(progress_data, true);
+        }
+        if since_last >= self.min_interval {
+            return self.emit_now(progress_data, false);
+        }
+
+        if self.pending.replace(progress_data).is_some() {
+            self.stats.coalesced += 1;
+        }
+        if self.flush_scheduled {
+            Decision::Hold
+        } else {
+            self.flush_scheduled = true;
+            Decision::Schedule(self.min_interval - since_last)
+        }
+    }
+
+    fn emit_now(&mut self, progress_data: ProgressData, immediate: bool) -> Decision {
+        // Anything held back is older than this update
+        if self.pending.take().is_some() {
+            self.stats.coalesced += 1;
+        }
+        if immediate {
+            self.stats.immediate += 1;
+        }
+        self.stats.emitted += 1;
+        self.last_emitted = Some((Instant::now(), progress_data));
+        Decision::Emit
+    }
+
+    fn take_pending(&mut self) -> Option<ProgressData> {
+        self.flush_scheduled = false;
+        let progress_data = self.pending.take()?;
+        self.stats.emitted += 1;
+        self.last_emitted = Some((Instant::now(), progress_data.clone()));
+        Some(progress_data)
+    }
+}
+
+// Play/pause changes and seeks must not wait for the next slot
+fn is_transition(last: &ProgressData, elapsed: Duration, next: &ProgressData) -> bool {
+    if last.is_playing != next.is_playing {
+        return true;
+    }
+    let expected = expected_position(last.position, last.is_playing, elapsed);
+    (next.position as i64 - expected as i64).abs() > SEEK_THRESHOLD_MS
+}
+
+// Emit progress to the overlay window, coalescing bursts
+pub fn emit_progress<R: Runtime>(app: &AppHandle<R>, progress_data: ProgressData) {
+    let throttle = match app.try_state::<SharedProgressThrottle>() {
+        Some(throttle) => throttle.inner().clone(),
+        None => return emit(app, progress_data),
+    };
+
+    let decision = match throttle.lock() {
+        Ok(mut t) => t.offer(progress_data.clone()),
+        Err(_) => Decision::Emit,
+    };
+
+    match decision {
+        Decision::Emit => emit(app, progress_data),
+        Decision::Hold => {}
+        Decision::Schedule(delay) => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(delay).await;
+                let pending = throttle.lock().ok().and_then(|mut t| t.take_pending());
+                if let Some(progress_data) = pending {
+                    emit(&app, progress_data);
+                }
+            });
+        }
+    }
+}
+
+// Only the overlay renders progress, the settings window doesn't need to wake up
+fn emit<R: Runtime>(app: &AppHandle<R>, progress_data: ProgressData) {
+    let _ = app.emit_to("main", "progress-update", ProgressEvent { progress_data });
+}
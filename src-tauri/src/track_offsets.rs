@@ -0,0 +1,67 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::config::write_json;
+use crate::TrackInfo;
+
+const OFFSETS_FILE_NAME: &str = "track-offsets.json";
+
+pub type SharedTrackOffsets = Arc<Mutex<TrackOffsets>>;
+
+// Returned by the track offset commands
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackOffsetInfo {
+    pub track: Option<String>, // "Artist - Title" of the current track
+    pub offset_ms: i64,
+}
+
+// Per-track timing corrections for provider lyrics that are consistently early or late
+#[derive(Debug, Default)]
+pub struct TrackOffsets {
+    offsets: BTreeMap<String, i64>,
+    path: Option<PathBuf>,
+}
+
+// Player id when available, otherwise artist + title so the offset survives re-sends
+pub fn track_key(track: &TrackInfo) -> String {
+    match &track.id {
+        Some(id) if !id.is_empty() => id.clone(),
+        _ => format!("{} - {}", track.artist.trim(), track.title.trim()).to_lowercase(),
+    }
+}
+
+impl TrackOffsets {
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = app.path().app_config_dir().ok().map(|dir| dir.join(OFFSETS_FILE_NAME));
+        let offsets = path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| {
+                serde_json::from_str(&contents)
+                    .map_err(|e| eprintln!("Invalid track offsets file: {}", e))
+                    .ok()
+            })
+            .unwrap_or_default();
+        Self { offsets, path }
+    }
+
+    pub fn get(&self, track: &TrackInfo) -> i64 {
+        self.offsets.get(&track_key(track)).copied().unwrap_or(0)
+    }
+
+    // Store the new offset (0 removes the entry) and write the file
+    pub fn set(&mut self, track: &TrackInfo, offset_ms: i64) -> Result<(), String> {
+        let key = track_key(track);
+        if offset_ms == 0 {
+            self.offsets.remove(&key);
+        } else {
+            self.offsets.insert(key, offset_ms);
+        }
+        let path = self.path.as_ref().ok_or("App config dir unavailable")?;
+        write_json(path, &self.offsets)
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::patch::LyricsPatchEvent;
+use crate::{LyricLine, LyricsData};
+
+// Translation languages shown in the overlay, in display order (empty = all of them)
+pub type SharedTranslationLanguages = Arc<Mutex<Vec<String>>>;
+
+// Trimmed, without empty entries and duplicates, keeping the first occurrence
+pub fn normalize_languages(languages: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for language in languages {
+        let language = language.trim();
+        if !language.is_empty() && !normalized.iter().any(|l| l.eq_ignore_ascii_case(language)) {
+            normalized.push(language.to_string());
+        }
+    }
+    normalized
+}
+
+pub fn selected_languages<R: Runtime>(app: &AppHandle<R>) -> Vec<String> {
+    app.try_state::<SharedTranslationLanguages>()
+        .and_then(|languages| languages.lock().ok().map(|l| l.clone()))
+        .unwrap_or_default()
+}
+
+// Entry of the first selected language present, or the first one when nothing is selected
+fn preferred<'a>(translations: &'a BTreeMap<String, String>, languages: &[String]) -> Option<&'a String> {
+    if languages.is_empty() {
+        return translations.values().next();
+    }
+    languages.iter().find_map(|l| {
+        translations.iter().find(|(language, _)| language.eq_ignore_ascii_case(l)).map(|(_, text)| text)
+    })
+}
+
+// `transText` is the compatibility alias: filled with the preferred translation when absent
+pub fn fill_alias(line: &mut LyricLine, languages: &[String]) {
+    if line.trans_text.is_none() {
+        line.trans_text = preferred(&line.translations, languages).cloned();
+    }
+}
+
+// Drop the languages that aren't selected; an alias pointing at a dropped one follows the selection
+pub fn filter(trans_text: &mut Option<String>, translations: &mut BTreeMap<String, String>, languages: &[String]) {
+    if languages.is_empty() || translations.is_empty() {
+        return;
+    }
+    let mut removed = Vec::new();
+    translations.retain(|language, text| {
+        let keep = languages.iter().any(|l| l.eq_ignore_ascii_case(language));
+        if !keep {
+            removed.push(text.clone());
+        }
+        keep
+    });
+    if trans_text.as_ref().is_some_and(|t| removed.contains(t)) {
+        *trans_text = preferred(translations, languages).cloned();
+    }
+}
+
+// Copy of the lyrics as the overlay should see them: only the selected translations
+pub fn for_overlay(lyrics_data: &LyricsData, languages: &[String]) -> LyricsData {
+    let mut lyrics_data = lyrics_data.clone();
+    for line in &mut lyrics_data.lyrics {
+        filter(&mut line.trans_text, &mut line.translations, languages);
+    }
+    lyrics_data
+}
+
+// Same for a patch event
+pub fn patch_for_overlay(event: &LyricsPatchEvent, languages: &[String]) -> LyricsPatchEvent {
+    let mut event = event.clone();
+    for line in &mut event.lines {
+        filter(&mut line.trans_text, &mut line.translations, languages);
+    }
+    event
+}
+
+// Shorthand for the lyrics-update paths, which all use the current selection
+pub fn lyrics_for_overlay<R: Runtime>(app: &AppHandle<R>, lyrics_data: &LyricsData) -> LyricsData {
+    for_overlay(lyrics_data, &selected_languages(app))
+}
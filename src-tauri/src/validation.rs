@@ -0,0 +1,197 @@
+use serde::Serialize;
+
+use crate::patch::LyricsPatch;
+use crate::{LyricLine, LyricsData, ProgressData};
+
+// Longest track we accept (24h), anything above is a unit mixup or garbage
+const MAX_DURATION_MS: u64 = 24 * 60 * 60 * 1000;
+// Players report a position slightly past the end when a track finishes
+const POSITION_TOLERANCE_MS: u64 = 1000;
+
+// A single invalid field, `field` is the JSON path (e.g. `lyrics[3].endTime`)
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl FieldError {
+    pub(crate) fn new(field: impl Into<String>, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+pub fn validate_lyrics(data: &LyricsData) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if data.track.title.trim().is_empty() {
+        errors.push(FieldError::new("track.title", "empty", "Track title must not be empty"));
+    }
+    if data.track.duration == 0 || data.track.duration > MAX_DURATION_MS {
+        errors.push(FieldError::new(
+            "track.duration",
+            "out_of_range",
+            format!("Duration must be between 1 and {} ms", MAX_DURATION_MS),
+        ));
+    }
+
+    // Runs after normalize.rs (sorting, merging), paths point at the lines as the player sent them
+    for (stored_index, line) in data.lyrics.iter().enumerate() {
+        let i = line.payload_index().unwrap_or(stored_index);
+        if line.start_time < 0 {
+            errors.push(FieldError::new(
+                format!("lyrics[{}].startTime", i),
+                "out_of_range",
+                "Start time must not be negative",
+            ));
+        }
+        if let Some(end_time) = line.end_time {
+            if end_time < line.start_time {
+                errors.push(FieldError::new(
+                    format!("lyrics[{}].endTime", i),
+                    "end_before_start",
+                    format!("End time {} is before start time {}", end_time, line.start_time),
+                ));
+            }
+        }
+        for language in line.translations.keys() {
+            if language.trim().is_empty() {
+                errors.push(FieldError::new(
+                    format!("lyrics[{}].translations", i),
+                    "empty_language",
+                    "Translation language must not be empty",
+                ));
+            }
+        }
+        if line.singer.as_deref().is_some_and(|singer| singer.trim().is_empty()) {
+            errors.push(FieldError::new(
+                format!("lyrics[{}].singer", i),
+                "empty",
+                "Singer must be omitted or not empty",
+            ));
+        }
+        errors.extend(validate_segments(i, line));
+    }
+
+    errors
+}
+
+// Segments must be ordered and stay within their line
+fn validate_segments(line_index: usize, line: &LyricLine) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    let mut previous_end: Option<i64> = None;
+    for (i, segment) in line.segments.iter().enumerate() {
+        let field = |name: &str| format!("lyrics[{}].segments[{}].{}", line_index, i, name);
+
+        if segment.end_time < segment.start_time {
+            errors.push(FieldError::new(
+                field("endTime"),
+                "end_before_start",
+                format!("End time {} is before start time {}", segment.end_time, segment.start_time),
+            ));
+        }
+        if segment.start_time < line.start_time {
+            errors.push(FieldError::new(
+                field("startTime"),
+                "outside_line",
+                format!("Segment starts at {}, before its line ({})", segment.start_time, line.start_time),
+            ));
+        }
+        if let Some(line_end) = line.end_time {
+            if segment.end_time > line_end {
+                errors.push(FieldError::new(
+                    field("endTime"),
+                    "outside_line",
+                    format!("Segment ends at {}, after its line ({})", segment.end_time, line_end),
+                ));
+            }
+        }
+        if let Some(previous) = previous_end {
+            if segment.start_time < previous {
+                errors.push(FieldError::new(
+                    field("startTime"),
+                    "overlapping",
+                    format!("Segment starts at {}, before the previous one ends ({})", segment.start_time, previous),
+                ));
+            }
+        }
+        if segment.singer.as_deref().is_some_and(|singer| singer.trim().is_empty()) {
+            errors.push(FieldError::new(field("singer"), "empty", "Singer must be omitted or not empty"));
+        }
+        previous_end = Some(segment.end_time);
+    }
+
+    errors
+}
+
+pub fn validate_patch(patch: &LyricsPatch) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if patch.track_id.trim().is_empty() {
+        errors.push(FieldError::new("trackId", "empty", "Track id must not be empty"));
+    }
+    if patch.lines.is_empty() {
+        errors.push(FieldError::new("lines", "empty", "At least one line must be patched"));
+    }
+    for (i, line) in patch.lines.iter().enumerate() {
+        if line.index.is_none() && line.start_time.is_none() {
+            errors.push(FieldError::new(
+                format!("lines[{}]", i),
+                "missing_target",
+                "Either index or startTime is required",
+            ));
+        }
+        if line.trans_text.is_none() && line.pron_text.is_none() && line.translations.is_empty() {
+            errors.push(FieldError::new(
+                format!("lines[{}]", i),
+                "empty",
+                "Nothing to patch, expected transText, pronText and/or translations",
+            ));
+        }
+        if line.translations.keys().any(|language| language.trim().is_empty()) {
+            errors.push(FieldError::new(
+                format!("lines[{}].translations", i),
+                "empty_language",
+                "Translation language must not be empty",
+            ));
+        }
+    }
+
+    errors
+}
+
+pub fn validate_progress(data: &ProgressData) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    // Also bounds the arithmetic on positions downstream (latency, clock, seek detection)
+    if data.position > MAX_DURATION_MS {
+        errors.push(FieldError::new(
+            "position",
+            "out_of_range",
+            format!("Position must be at most {} ms", MAX_DURATION_MS),
+        ));
+    }
+    if let Some(duration) = data.duration {
+        if duration > MAX_DURATION_MS {
+            errors.push(FieldError::new(
+                "duration",
+                "out_of_range",
+                format!("Duration must be at most {} ms", MAX_DURATION_MS),
+            ));
+        } else if duration > 0 && data.position <= MAX_DURATION_MS && data.position > duration + POSITION_TOLERANCE_MS {
+            errors.push(FieldError::new(
+                "position",
+                "out_of_range",
+                format!("Position {} is past the track duration ({})", data.position, duration),
+            ));
+        }
+    }
+
+    errors
+}
@@ -0,0 +1,123 @@
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::http::StatusCode;
+use axum::response::Response;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::Runtime;
+use tokio::sync::broadcast;
+
+use crate::api::{Ack, ApiError};
+use crate::commands::{self, CommandResult, QueuedCommand};
+use crate::patch::LyricsPatch;
+use crate::{ingest_lyrics, ingest_lyrics_patch, ingest_progress, AppState, LyricsData, ProgressData};
+
+// Capacity of the server -> client push channel
+const PUSH_CHANNEL_CAPACITY: usize = 32;
+
+pub type ClientPush = broadcast::Sender<ServerMessage>;
+
+pub fn push_channel() -> ClientPush {
+    broadcast::channel(PUSH_CHANNEL_CAPACITY).0
+}
+
+// Frames sent by the player extension, same payloads as POST /lyrics and /progress
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum ClientMessage {
+    Lyrics(LyricsData),
+    Progress(ProgressData),
+    LyricsPatch(LyricsPatch),
+    #[serde(rename_all = "camelCase")]
+    CommandAck {
+        id: u64,
+        #[serde(flatten)]
+        result: CommandResult,
+    },
+    Ping,
+}
+
+// Frames pushed back to connected clients
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum ServerMessage {
+    Ack(Ack),
+    Error(ApiError),
+    Pong,
+    ResendRequest, // Ask the extension to send the current lyrics/progress again
+    Command(QueuedCommand), // Player command from the overlay, answered with `commandAck`
+}
+
+pub async fn handle_ws<R: Runtime>(
+    ws: WebSocketUpgrade,
+    axum::extract::State(state): axum::extract::State<Arc<AppState<R>>>,
+) -> Response {
+    ws.on_upgrade(move |socket| handle_socket(socket, state))
+}
+
+async fn handle_socket<R: Runtime>(mut socket: WebSocket, state: Arc<AppState<R>>) {
+    let mut pushed = state.client_push.subscribe();
+
+    if let Some(message) = greeting(&state) {
+        if send(&mut socket, &message).await.is_err() {
+            return;
+        }
+    }
+
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => {
+                let text = match incoming {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue, // Ping/pong are answered by axum, binary frames are ignored
+                };
+
+                let reply = handle_text(&state, &text);
+                if send(&mut socket, &reply).await.is_err() {
+                    break;
+                }
+            }
+            outgoing = pushed.recv() => {
+                match outgoing {
+                    Ok(message) => {
+                        if send(&mut socket, &message).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }
+    }
+}
+
+// Nothing to show yet -> ask the freshly connected client for the current track
+pub(crate) fn greeting<R: Runtime>(state: &AppState<R>) -> Option<ServerMessage> {
+    let has_lyrics = state.now_playing.lock().map(|s| s.lyrics.is_some()).unwrap_or(false);
+    (!has_lyrics).then_some(ServerMessage::ResendRequest)
+}
+
+// Shared with the IPC transport, which speaks the same frames
+pub(crate) fn handle_text<R: Runtime>(state: &AppState<R>, text: &str) -> ServerMessage {
+    match serde_json::from_str::<ClientMessage>(text) {
+        Ok(ClientMessage::Lyrics(lyrics_data)) => reply(ingest_lyrics(state, lyrics_data)),
+        Ok(ClientMessage::Progress(progress_data)) => reply(ingest_progress(state, progress_data)),
+        Ok(ClientMessage::LyricsPatch(patch)) => reply(ingest_lyrics_patch(state, patch)),
+        Ok(ClientMessage::CommandAck { id, result }) => reply(commands::acknowledge(&state.app_handle, id, result)),
+        Ok(ClientMessage::Ping) => ServerMessage::Pong,
+        Err(e) => ServerMessage::Error(ApiError::new(StatusCode::BAD_REQUEST, "invalid_payload", e.to_string())),
+    }
+}
+
+fn reply(result: Result<Ack, ApiError>) -> ServerMessage {
+    match result {
+        Ok(ack) => ServerMessage::Ack(ack),
+        Err(e) => ServerMessage::Error(e),
+    }
+}
+
+async fn send(socket: &mut WebSocket, message: &ServerMessage) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(message).map_err(axum::Error::new)?;
+    socket.send(Message::Text(text)).await
+}
@@ -5,7 +5,20 @@ import { invoke } from "@tauri-apps/api/core";
 import { check } from "@tauri-apps/plugin-updater";
 import { relaunch } from "@tauri-apps/plugin-process";
 import "./App.css";
-import type { TrackInfo, LyricLine, LyricsEvent, ProgressEvent } from "./types";
+import type {
+  TrackInfo,
+  LyricLine,
+  LyricsData,
+  LyricsEvent,
+  LyricsPatchEvent,
+  LineChangeEvent,
+  ClockSnapshot,
+  PlayerCommand,
+  NowPlayingCleared,
+  ProgressData,
+  ProgressEvent,
+  StateSnapshot,
+} from "./types";
 import SettingsPanelNew from "./SettingsPanel";
 
 // Default settings
@@ -111,6 +124,22 @@ const defaultSettings = {
 export type OverlaySettings = typeof defaultSettings;
 export { defaultSettings };
 
+// Named settings snapshots, switchable from the settings panel or POST /control/profile
+const PROFILES_KEY = "overlay-profiles-v1";
+export type SettingsProfiles = Record<string, Partial<OverlaySettings>>;
+
+export function loadProfiles(): SettingsProfiles {
+  try {
+    return JSON.parse(localStorage.getItem(PROFILES_KEY) || "{}");
+  } catch {
+    return {};
+  }
+}
+
+export function saveProfiles(profiles: SettingsProfiles) {
+  localStorage.setItem(PROFILES_KEY, JSON.stringify(profiles));
+}
+
 // Localization Data
 const strings = {
   ko: {
@@ -392,8 +421,13 @@ function App() {
   const [track, setTrack] = useState<TrackInfo | null>(null);
   const [lyrics, setLyrics] = useState<LyricLine[]>([]);
   const [_isSynced, setIsSynced] = useState<boolean>(true);
-  const [progress, setProgress] = useState<number>(0);
+  // 현재 가사 줄은 백엔드 재생 시계가 줄 경계마다 line-change 이벤트로 알려줌
+  const [activeLineIndex, setActiveLineIndex] = useState<number>(-1);
   const [isPlaying, setIsPlaying] = useState<boolean>(false);
+  // 오버레이에 표시할 번역 언어와 순서 (비어 있으면 전부)
+  const [translationLanguages, setTranslationLanguages] = useState<string[]>([]);
+  // 마지막으로 받은 재생 위치와 받은 시각 (가라오케 하이라이트 기준점)
+  const [playbackAnchor, setPlaybackAnchor] = useState({ position: 0, at: 0 });
   const [remaining, setRemaining] = useState<number>(Infinity);
   const [nextTrack, setNextTrack] = useState<{
     title: string;
@@ -448,65 +482,153 @@ function App() {
     return () => window.removeEventListener("storage", handleStorageChange);
   }, []);
 
-  // Find active lyric line index
-  const activeLineIndex = useMemo(() => {
-    if (lyrics.length === 0) return -1;
-    for (let i = lyrics.length - 1; i >= 0; i--) {
-      if (progress >= lyrics[i].startTime) {
-        return i;
-      }
-    }
-    return -1;
-  }, [lyrics, progress]);
-
   // Get the active lyric line only
   const activeLine = useMemo(() => {
     if (activeLineIndex < 0 || activeLineIndex >= lyrics.length) return null;
     return lyrics[activeLineIndex];
   }, [lyrics, activeLineIndex]);
 
+  // 가수/파트별 색상·정렬 인덱스 (등장 순서)
+  const singerOrder = useMemo(() => {
+    const singers: string[] = [];
+    for (const line of lyrics) {
+      for (const singer of [line.singer, ...(line.segments ?? []).map((s) => s.singer)]) {
+        if (singer && !singers.includes(singer)) singers.push(singer);
+      }
+    }
+    return singers;
+  }, [lyrics]);
+  const singerClass = (singer?: string | null) => {
+    const index = singer ? singerOrder.indexOf(singer) : -1;
+    return index > 0 ? `singer-${index % 4}` : "";
+  };
+
+  // 인트로 카운트다운은 초 단위로 다시 그려야 함
+  const [, setCountdownTick] = useState(0);
+  useEffect(() => {
+    if (activeLine?.kind !== "intro" || !isPlaying) return;
+    const timer = setInterval(() => setCountdownTick((tick) => tick + 1), 250);
+    return () => clearInterval(timer);
+  }, [activeLine, isPlaying]);
+
   // Hover state for opacity control
   const [isHovering, setIsHovering] = useState(false);
 
   // Listen for events from Rust backend
   useEffect(() => {
+    const applyLyricsData = (lyricsData: LyricsData) => {
+      setTrack(lyricsData.track);
+      // 싱크 데이터가 없는 일반 가사는 백엔드가 타이밍을 추정한 경우에만 표시
+      if (lyricsData.isSynced || lyricsData.estimatedTiming) {
+        setLyrics(lyricsData.lyrics);
+        setIsSynced(true);
+      } else {
+        setLyrics([]);
+        setIsSynced(false);
+      }
+    };
+
+    const applyProgressData = (progressData: ProgressData) => {
+      setIsPlaying(progressData.isPlaying);
+      setPlaybackAnchor({ position: progressData.position, at: performance.now() });
+      if (progressData.remaining !== undefined) {
+        setRemaining(progressData.remaining);
+      }
+      if (progressData.nextTrack !== undefined) {
+        setNextTrack(progressData.nextTrack);
+      }
+    };
+
     const unlistenLyrics = listen<LyricsEvent>("lyrics-update", (event) => {
       const payload = event.payload;
       if (payload.lyricsData) {
-        setTrack(payload.lyricsData.track);
-        // 싱크 데이터가 없는 일반 가사는 표시하지 않음
-        if (payload.lyricsData.isSynced) {
-          setLyrics(payload.lyricsData.lyrics);
-          setIsSynced(true);
-        } else {
-          setLyrics([]);
-          setIsSynced(false);
-        }
+        applyLyricsData(payload.lyricsData);
       }
     });
 
+    // 활성 소스가 바뀌었는데 새 소스가 아직 보내지 않은 가사/진행 상태는 비움
+    const unlistenCleared = listen<NowPlayingCleared>("now-playing-cleared", (event) => {
+      if (event.payload.lyrics) {
+        setTrack(null);
+        setLyrics([]);
+        setActiveLineIndex(-1);
+      }
+      if (event.payload.progress) {
+        setIsPlaying(false);
+        setRemaining(Infinity);
+      }
+    });
+
+    // 번역/발음만 갱신: 가사 전체를 다시 설정하지 않고 해당 줄만 병합
+    const unlistenLyricsPatch = listen<LyricsPatchEvent>("lyrics-patch", (event) => {
+      const { lines } = event.payload;
+      setLyrics((prev) => {
+        const next = [...prev];
+        for (const line of lines) {
+          if (line.index >= next.length) continue;
+          next[line.index] = {
+            ...next[line.index],
+            transText: line.transText ?? undefined,
+            pronText: line.pronText ?? undefined,
+            translations: line.translations,
+          };
+        }
+        return next;
+      });
+    });
+
     const unlistenProgress = listen<ProgressEvent>(
       "progress-update",
       (event) => {
         const payload = event.payload;
         if (payload.progressData) {
-          setProgress(payload.progressData.position);
-          setIsPlaying(payload.progressData.isPlaying);
-          if (payload.progressData.remaining !== undefined) {
-            setRemaining(payload.progressData.remaining);
-          }
-          if (payload.progressData.nextTrack !== undefined) {
-            setNextTrack(payload.progressData.nextTrack);
-          }
+          applyProgressData(payload.progressData);
         }
       }
     );
 
+    const unlistenLineChange = listen<LineChangeEvent>("line-change", (event) => {
+      setActiveLineIndex(event.payload.current ?? -1);
+      setPlaybackAnchor({ position: event.payload.position, at: performance.now() });
+    });
+
+    // Catch up with whatever is already playing (e.g. after a reload)
+    invoke<ClockSnapshot>("get_playback_clock")
+      .then((clock) => setActiveLineIndex(clock.current ?? -1))
+      .catch(console.error);
+    invoke<StateSnapshot>("get_current_state")
+      .then((snapshot) => {
+        if (snapshot.lyricsData) {
+          applyLyricsData(snapshot.lyricsData);
+        }
+        if (snapshot.progressData) {
+          applyProgressData(snapshot.progressData);
+        }
+      })
+      .catch(console.error);
+
+    invoke<string[]>("get_translation_languages")
+      .then(setTranslationLanguages)
+      .catch(console.error);
+    const unlistenTranslationLanguages = listen<string[]>("translation-languages", (event) => {
+      setTranslationLanguages(event.payload);
+    });
+
     // Listen for lock state changes from Tray
     const unlistenLockUpdate = listen<boolean>("lock-state-update", (event) => {
       setSettings((prev) => ({ ...prev, isLocked: event.payload }));
     });
 
+    // Profile switch from the remote control API (applied by the overlay, synced to settings via storage)
+    const unlistenProfile = listen<{ name: string }>("profile-switch", (event) => {
+      if (isSettingsWindow) return;
+      const profile = loadProfiles()[event.payload.name];
+      if (profile) {
+        // Lock state is not part of a profile
+        setSettings((prev) => ({ ...defaultSettings, ...profile, isLocked: prev.isLocked }));
+      }
+    });
+
     // Listen for hover state from backend (for transparency)
     const unlistenHover = listen<boolean>("overlay-hover", (event) => {
       setIsHovering(event.payload);
@@ -526,8 +648,13 @@ function App() {
 
     return () => {
       unlistenLyrics.then((fn) => fn());
+      unlistenLyricsPatch.then((fn) => fn());
+      unlistenCleared.then((fn) => fn());
+      unlistenTranslationLanguages.then((fn) => fn());
+      unlistenLineChange.then((fn) => fn());
       unlistenProgress.then((fn) => fn());
       unlistenLockUpdate.then((fn) => fn());
+      unlistenProfile.then((fn) => fn());
       unlistenHover.then((fn) => fn());
     };
   }, []); // Run once
@@ -656,16 +783,28 @@ function App() {
       line.pronText &&
       line.pronText.trim() !== "" &&
       line.pronText !== line.text;
-    // transText가 존재하고, 빈 문자열이 아니며, 원어/발음과 다르면 표시
-    const hasTransText =
-      line.transText &&
-      line.transText.trim() !== "" &&
-      line.transText !== line.text &&
-      line.transText !== line.pronText;
+    // 번역이 존재하고, 빈 문자열이 아니며, 원어/발음과 다르면 표시
+    const isShownTranslation = (text?: string): text is string =>
+      !!text &&
+      text.trim() !== "" &&
+      text !== line.text &&
+      text !== line.pronText;
+    // 언어별 번역이 있으면 선택한 언어 순서대로, 없으면 transText 하나
+    const translations = line.translations ?? {};
+    const keys = Object.keys(translations);
+    const languages = translationLanguages.length > 0
+      ? translationLanguages
+          .map((language) => keys.find((key) => key.toLowerCase() === language.toLowerCase()))
+          .filter((key): key is string => key !== undefined)
+      : keys;
+    const candidates = languages.length > 0
+      ? languages.map((language) => translations[language])
+      : [line.transText];
     return {
       main: line.text || "",
       phonetic: hasPronText ? line.pronText : null,
-      translation: hasTransText ? line.transText : null,
+      background: line.backgroundText?.trim() ? line.backgroundText : null,
+      translations: candidates.filter(isShownTranslation),
     };
   };
 
@@ -759,6 +898,48 @@ function App() {
 
   const display = activeLine ? getDisplayText(activeLine) : null;
 
+  // 간주/인트로 표시: 세 개의 점이 구간 동안 차례로 채워지고, 인트로는 마지막 5초를 카운트다운
+  const renderMarker = (line: LyricLine) => {
+    const now = playbackAnchor.position + (isPlaying ? performance.now() - playbackAnchor.at : 0);
+    const end = line.endTime ?? line.startTime;
+    const remaining = Math.ceil((end - now) / 1000);
+    if (line.kind === "intro" && remaining > 0 && remaining <= 5) {
+      return <span className="lyric-countdown">{remaining}</span>;
+    }
+    const third = Math.max(1, (end - line.startTime) / 3);
+    return [0, 1, 2].map((i) => (
+      <span
+        key={`${i}-${playbackAnchor.at}`}
+        className="lyric-segment lyric-interlude-dot"
+        style={{
+          animationDuration: `${third}ms`,
+          animationDelay: `${line.startTime + third * i - now}ms`,
+          animationPlayState: isPlaying ? "running" : "paused",
+        }}
+      >
+        ●
+      </span>
+    ));
+  };
+
+  // 단어/음절 하이라이트: 각 구간을 CSS 애니메이션으로 채우고, 기준점이 바뀌면 key로 다시 시작
+  const renderSegments = (line: LyricLine) => {
+    const now = playbackAnchor.position + (isPlaying ? performance.now() - playbackAnchor.at : 0);
+    return (line.segments ?? []).map((segment, i) => (
+      <span
+        key={`${i}-${playbackAnchor.at}`}
+        className={`lyric-segment ${singerClass(segment.singer)}`}
+        style={{
+          animationDuration: `${Math.max(1, segment.endTime - segment.startTime)}ms`,
+          animationDelay: `${segment.startTime - now}ms`,
+          animationPlayState: isPlaying ? "running" : "paused",
+        }}
+      >
+        {segment.text}
+      </span>
+    ));
+  };
+
   // Alignment classes
   const alignClass =
     settings.textAlign === "left"
@@ -1052,8 +1233,25 @@ function App() {
             });
           }
 
+          // 듀엣: 첫 번째 가수는 왼쪽, 두 번째 가수는 오른쪽, 그 외(함께 부르는 줄 등)는 기본 정렬
+          const duetAlign = (singer?: string | null) => {
+            if (singerOrder.length < 2 || !singer) return 'inherit';
+            const index = singerOrder.indexOf(singer);
+            return index === 0 ? 'flex-start' : index === 1 ? 'flex-end' : 'inherit';
+          };
+
           // 각 줄에 대해 렌더링 (세트 단위로 wrapper)
           const renderLyricSet = (lineInfo: { line: LyricLine; index: number; isActive: boolean }, setIndex: number, totalSets: number) => {
+            // 간주/인트로 줄은 현재 줄일 때만 표시
+            if (lineInfo.line.kind && lineInfo.line.kind !== "lyric") {
+              if (!lineInfo.isActive || !settings.showOriginal) return null;
+              return (
+                <div key={`set-${lineInfo.index}`} className="lyrics-set" style={{ display: 'flex', flexDirection: 'column', alignItems: 'inherit', marginBottom: setIndex === totalSets - 1 ? 0 : `${settings.lyricsSetGap}px` }}>
+                  <div className="lyric-line original active interlude">{renderMarker(lineInfo.line)}</div>
+                </div>
+              );
+            }
+
             const displayData = getDisplayText(lineInfo.line);
             const fadeOpacity = lineInfo.isActive || !settings.fadeNonActiveLyrics ? 1 : 0.5;
             const isLastSet = setIndex === totalSets - 1;
@@ -1064,15 +1262,26 @@ function App() {
                 switch (element) {
                   case "original":
                     if (!settings.showOriginal || !displayData.main) return null;
-                    return (
+                    return [
                       <div
                         key={`original-${lineInfo.index}`}
-                        className={`lyric-line original ${lineInfo.isActive ? 'active' : 'inactive'}`}
+                        className={`lyric-line original ${lineInfo.isActive ? 'active' : 'inactive'} ${singerClass(lineInfo.line.singer)}`}
                         style={{ opacity: fadeOpacity }}
                       >
-                        {displayData.main}
-                      </div>
-                    );
+                        {lineInfo.isActive && lineInfo.line.segments?.length
+                          ? renderSegments(lineInfo.line)
+                          : displayData.main}
+                      </div>,
+                      displayData.background && (
+                        <div
+                          key={`background-${lineInfo.index}`}
+                          className={`lyric-line background ${lineInfo.isActive ? 'active' : 'inactive'}`}
+                          style={{ opacity: fadeOpacity }}
+                        >
+                          {displayData.background}
+                        </div>
+                      ),
+                    ];
                   case "phonetic":
                     if (!settings.showPhonetic || !displayData.phonetic) return null;
                     return (
@@ -1085,16 +1294,16 @@ function App() {
                       </div>
                     );
                   case "translation":
-                    if (!settings.showTranslation || !displayData.translation) return null;
-                    return (
+                    if (!settings.showTranslation || displayData.translations.length === 0) return null;
+                    return displayData.translations.map((translation, i) => (
                       <div
-                        key={`translation-${lineInfo.index}`}
+                        key={`translation-${lineInfo.index}-${i}`}
                         className={`lyric-line translation ${lineInfo.isActive ? 'active' : 'inactive'}`}
                         style={{ opacity: fadeOpacity }}
                       >
-                        {displayData.translation}
+                        {translation}
                       </div>
-                    );
+                    ));
                   default:
                     return null;
                 }
@@ -1103,16 +1312,21 @@ function App() {
 
             if (elements.length === 0) return null;
 
-            // 세트를 wrapper로 감싸서 간격 적용
+            // 세트를 wrapper로 감싸서 간격 적용 (잠금 해제 시 클릭하면 해당 줄로 이동)
             return (
               <div
                 key={`set-${lineInfo.index}`}
                 className="lyrics-set"
+                onClick={settings.isLocked ? undefined : () => {
+                  const command: PlayerCommand = { type: "seek", positionMs: Math.max(0, lineInfo.line.startTime) };
+                  invoke("enqueue_player_command", { command }).catch(console.error);
+                }}
                 style={{
+                  cursor: settings.isLocked ? undefined : 'pointer',
                   marginBottom: isLastSet ? 0 : `${settings.lyricsSetGap}px`,
                   display: 'flex',
                   flexDirection: 'column',
-                  alignItems: 'inherit',
+                  alignItems: duetAlign(lineInfo.line.singer),
                   gap: `${settings.lineGap}px`
                 }}
               >
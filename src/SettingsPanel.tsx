@@ -1,7 +1,9 @@
 import { useState, useEffect, useRef } from "react";
 import { enable, disable, isEnabled } from "@tauri-apps/plugin-autostart";
 import { invoke } from "@tauri-apps/api/core";
-import { defaultSettings, OverlaySettings } from "./App";
+import { listen } from "@tauri-apps/api/event";
+import { defaultSettings, OverlaySettings, loadProfiles, saveProfiles, SettingsProfiles } from "./App";
+import type { ArbitrationPolicy, SourceInfo, UnsyncedTiming } from "./types";
 import "./SettingsPanel.css";
 
 // 탭 타입 정의
@@ -22,6 +24,13 @@ const strings = {
         originalLyrics: "원어 가사",
         phoneticLyrics: "발음 가사",
         translationLyrics: "번역 가사",
+        translationLanguages: "번역 언어 (쉼표로 구분, 표시 순서)",
+        translationLanguagesDesc: "예: ko, en · 비워 두면 모든 언어 표시",
+        unsyncedTiming: "싱크 없는 가사",
+        unsyncedTimingDesc: "타임스탬프가 없는 가사를 곡 길이에 맞춰 표시",
+        unsyncedOff: "표시 안 함",
+        unsyncedWeighted: "글자 수로 추정",
+        unsyncedProportional: "균등하게 스크롤",
         trackInfo: "곡 정보",
         albumArt: "앨범아트",
 
@@ -103,6 +112,44 @@ const strings = {
         autoLock: "자동 잠금",
         autoLockDelay: "자동 잠금 지연",
 
+        connectionSection: "연결",
+        serverAddress: "서버 주소",
+        serverNotRunning: "실행 중이 아님",
+        serverStarting: "시작 중...",
+        serverError: "오류",
+        restartServer: "서버 재시작",
+        requireToken: "인증 토큰 필수",
+        requireTokenDesc: "토큰이 없는 요청을 거부합니다",
+        authToken: "인증 토큰",
+        sourcePolicy: "가사 소스 선택",
+        policyMostRecent: "최근 재생 우선",
+        policyPriority: "고정 우선순위",
+        policyManual: "직접 선택",
+        priorityOrder: "우선순위 (쉼표로 구분)",
+        manualSource: "소스",
+        noSources: "연결된 소스 없음",
+        rejectedOrigins: "차단된 요청",
+        rejectedOriginsNone: "없음",
+        positionOffset: "가사 싱크 오프셋",
+        measuredDelay: "측정된 전송 지연: {delay}ms",
+        measuredDelayNone: "전송 지연 측정값 없음 (sentAt 미전송)",
+        trackOffset: "이 곡 오프셋",
+        trackOffsetNone: "재생 중인 곡 없음",
+        earlier: "빠르게",
+        later: "느리게",
+        resetOffset: "초기화",
+        progressEvents: "재생 위치 이벤트",
+        progressEventsDesc: "수신 {received} · 즉시 {immediate} · 병합 {coalesced}",
+        regenerateToken: "토큰 재발급",
+        regenerateConfirm: "토큰을 재발급하면 확장 프로그램에 새 토큰을 입력해야 합니다. 계속하시겠습니까?",
+
+        profilesSection: "프로필",
+        profileName: "프로필 이름",
+        saveProfile: "현재 설정 저장",
+        applyProfile: "적용",
+        deleteProfile: "삭제",
+        noProfiles: "저장된 프로필 없음",
+
         advancedSection: "고급",
         customCSS: "사용자 정의 CSS",
         resetSettings: "설정 초기화",
@@ -133,6 +180,13 @@ const strings = {
         originalLyrics: "Original Lyrics",
         phoneticLyrics: "Phonetic Lyrics",
         translationLyrics: "Translation",
+        translationLanguages: "Translation languages (comma separated, in display order)",
+        translationLanguagesDesc: "e.g. ko, en · leave empty to show all",
+        unsyncedTiming: "Unsynced lyrics",
+        unsyncedTimingDesc: "Show lyrics without timestamps spread over the track",
+        unsyncedOff: "Don't show",
+        unsyncedWeighted: "Estimate by length",
+        unsyncedProportional: "Scroll evenly",
         trackInfo: "Track Info",
         albumArt: "Album Art",
 
@@ -214,6 +268,44 @@ const strings = {
         autoLock: "Auto lock",
         autoLockDelay: "Lock delay",
 
+        connectionSection: "Connection",
+        serverAddress: "Server Address",
+        serverNotRunning: "Not running",
+        serverStarting: "Starting...",
+        serverError: "Error",
+        restartServer: "Restart Server",
+        requireToken: "Require Auth Token",
+        requireTokenDesc: "Reject requests without the token",
+        authToken: "Auth Token",
+        sourcePolicy: "Lyrics Source",
+        policyMostRecent: "Most Recently Playing",
+        policyPriority: "Fixed Priority",
+        policyManual: "Manual",
+        priorityOrder: "Priority (comma separated)",
+        manualSource: "Source",
+        noSources: "No sources connected",
+        rejectedOrigins: "Blocked Requests",
+        rejectedOriginsNone: "None",
+        positionOffset: "Lyrics sync offset",
+        measuredDelay: "Measured delivery delay: {delay}ms",
+        measuredDelayNone: "No delivery delay measured (sentAt not sent)",
+        trackOffset: "Offset for this track",
+        trackOffsetNone: "No track playing",
+        earlier: "Earlier",
+        later: "Later",
+        resetOffset: "Reset",
+        progressEvents: "Progress events",
+        progressEventsDesc: "Received {received} · immediate {immediate} · coalesced {coalesced}",
+        regenerateToken: "Regenerate Token",
+        regenerateConfirm: "The extension will need the new token after regenerating. Continue?",
+
+        profilesSection: "Profiles",
+        profileName: "Profile name",
+        saveProfile: "Save current settings",
+        applyProfile: "Apply",
+        deleteProfile: "Delete",
+        noProfiles: "No saved profiles",
+
         advancedSection: "Advanced",
         customCSS: "Custom CSS",
         resetSettings: "Reset Settings",
@@ -558,6 +650,57 @@ function SettingSection({
     );
 }
 
+// 백엔드 서버 정보
+// TCP 필드는 IPC 전용 모드에서, ipcPath는 TCP 전용 모드에서 생략됨
+interface ServerInfo {
+    host?: string;
+    port?: number;
+    url?: string;
+    ipcPath?: string;
+    pid: number;
+}
+
+type ServerStatus =
+    | { state: "starting" }
+    | ({ state: "listening" } & ServerInfo)
+    | { state: "error"; message: string; retryInMs: number };
+
+interface AuthInfo {
+    token: string;
+    required: boolean;
+}
+
+// 전송 지연 측정값과 수동 오프셋
+interface LatencyInfo {
+    delayMs: number | null;
+    offsetMs: number;
+    samples: number;
+}
+
+// 곡별 오프셋 (track-offsets.json)
+interface TrackOffsetInfo {
+    track: string | null;
+    offsetMs: number;
+}
+
+// 재생 위치 이벤트 스로틀 카운터
+interface ThrottleStats {
+    received: number;
+    emitted: number;
+    immediate: number;
+    coalesced: number;
+}
+
+interface OriginRejectedEvent {
+    origin: string;
+    totalRejected: number;
+}
+
+interface OriginStats {
+    totalRejected: number;
+    lastOrigin: string | null;
+}
+
 // 메인 컴포넌트
 export default function SettingsPanelNew({
     settings,
@@ -571,14 +714,207 @@ export default function SettingsPanelNew({
     const t = strings[settings.language || "ko"];
     const [activeTab, setActiveTab] = useState<SettingsTab>("display");
     const [autoStart, setAutoStart] = useState(false);
+    const [serverStatus, setServerStatus] = useState<ServerStatus | null>(null);
+    const [authInfo, setAuthInfo] = useState<AuthInfo | null>(null);
+    const [lastRejected, setLastRejected] = useState<OriginRejectedEvent | null>(null);
+    const [sources, setSources] = useState<SourceInfo[]>([]);
+    const [progressStats, setProgressStats] = useState<ThrottleStats | null>(null);
+    const [latency, setLatency] = useState<LatencyInfo | null>(null);
+    const [trackOffset, setTrackOffset] = useState<TrackOffsetInfo | null>(null);
+    const [translationLanguages, setTranslationLanguages] = useState<string[]>([]);
+    const [unsyncedTiming, setUnsyncedTiming] = useState<UnsyncedTiming>("weighted");
+    const [sourcePolicy, setSourcePolicy] = useState<ArbitrationPolicy>({ mode: "mostRecentlyPlaying" });
+    const [profiles, setProfiles] = useState<SettingsProfiles>(loadProfiles);
+    const [selectedProfile, setSelectedProfile] = useState("");
+    const [profileName, setProfileName] = useState("");
     const contentRef = useRef<HTMLDivElement>(null);
 
+    const updateProfiles = (next: SettingsProfiles) => {
+        saveProfiles(next);
+        setProfiles(next);
+    };
+
+    const saveCurrentProfile = () => {
+        const name = profileName.trim();
+        if (!name) return;
+        // 잠금 상태는 프로필에 포함하지 않음
+        const profile: Partial<OverlaySettings> = { ...settings };
+        delete profile.isLocked;
+        updateProfiles({ ...profiles, [name]: profile });
+        setSelectedProfile(name);
+        setProfileName("");
+    };
+
+    const applyProfile = () => {
+        const profile = profiles[selectedProfile];
+        if (profile) {
+            onSettingsChange({ ...defaultSettings, ...profile, isLocked: settings.isLocked });
+        }
+    };
+
+    const deleteProfile = () => {
+        const next = { ...profiles };
+        delete next[selectedProfile];
+        updateProfiles(next);
+        setSelectedProfile("");
+    };
+
     useEffect(() => {
         isEnabled()
             .then(setAutoStart)
             .catch(console.error);
+        invoke<ServerStatus>("get_server_status")
+            .then(setServerStatus)
+            .catch(console.error);
+        invoke<AuthInfo>("get_auth_info")
+            .then(setAuthInfo)
+            .catch(console.error);
+
+        // 설정 창을 열기 전에 차단된 요청도 표시
+        invoke<OriginStats>("get_origin_stats")
+            .then((stats) => {
+                if (stats.lastOrigin !== null) {
+                    setLastRejected({ origin: stats.lastOrigin, totalRejected: stats.totalRejected });
+                }
+            })
+            .catch(console.error);
+
+        invoke<ArbitrationPolicy>("get_source_policy")
+            .then(setSourcePolicy)
+            .catch(console.error);
+
+        invoke<string[]>("get_translation_languages")
+            .then(setTranslationLanguages)
+            .catch(console.error);
+
+        invoke<UnsyncedTiming>("get_unsynced_timing")
+            .then(setUnsyncedTiming)
+            .catch(console.error);
+
+        const refreshSources = () => {
+            invoke<SourceInfo[]>("get_sources")
+                .then(setSources)
+                .catch(console.error);
+        };
+        refreshSources();
+
+        const refreshStats = () => {
+            invoke<ThrottleStats>("get_progress_stats")
+                .then(setProgressStats)
+                .catch(console.error);
+            invoke<LatencyInfo>("get_latency_info")
+                .then(setLatency)
+                .catch(console.error);
+        };
+        refreshStats();
+        const statsTimer = setInterval(refreshStats, 2000);
+
+        // 허용되지 않은 출처에서 온 요청 알림
+        const unlistenRejected = listen<OriginRejectedEvent>("origin-rejected", (event) => {
+            setLastRejected(event.payload);
+        });
+        const unlistenSource = listen<string>("source-change", refreshSources);
+        const refreshTrackOffset = () => {
+            invoke<TrackOffsetInfo>("get_track_offset")
+                .then(setTrackOffset)
+                .catch(console.error);
+        };
+        refreshTrackOffset();
+        const unlistenTrack = listen("track-change", refreshTrackOffset);
+        const unlistenServer = listen<ServerStatus>("server-status", (event) => {
+            setServerStatus(event.payload);
+        });
+        return () => {
+            clearInterval(statsTimer);
+            unlistenServer.then((fn) => fn());
+            unlistenRejected.then((fn) => fn());
+            unlistenSource.then((fn) => fn());
+            unlistenTrack.then((fn) => fn());
+        };
     }, []);
 
+    // 드래그 중에는 메모리 값만 바꾸고, 멈춘 뒤 한 번만 backend.json에 저장
+    const saveOffsetTimer = useRef<number | undefined>(undefined);
+    const changePositionOffset = (offsetMs: number) => {
+        setLatency((prev) => prev && { ...prev, offsetMs });
+        invoke("set_position_offset", { offsetMs }).catch(console.error);
+        window.clearTimeout(saveOffsetTimer.current);
+        saveOffsetTimer.current = window.setTimeout(() => {
+            saveOffsetTimer.current = undefined;
+            invoke("save_position_offset").catch(console.error);
+        }, 500);
+    };
+    useEffect(() => () => {
+        // 창이 닫히기 전에 저장 대기 중인 값을 바로 저장
+        if (saveOffsetTimer.current !== undefined) {
+            window.clearTimeout(saveOffsetTimer.current);
+            invoke("save_position_offset").catch(console.error);
+        }
+    }, []);
+
+    const nudgeTrackOffset = (deltaMs: number) => {
+        invoke<TrackOffsetInfo>("nudge_track_offset", { deltaMs })
+            .then(setTrackOffset)
+            .catch(console.error);
+    };
+
+    const resetTrackOffset = () => {
+        invoke<TrackOffsetInfo>("reset_track_offset")
+            .then(setTrackOffset)
+            .catch(console.error);
+    };
+
+    const changeTranslationLanguages = (value: string) => {
+        const languages = value.split(",").map((l) => l.trim()).filter((l) => l.length > 0);
+        invoke<string[]>("set_translation_languages", { languages })
+            .then(setTranslationLanguages)
+            .catch(console.error);
+    };
+
+    const changeUnsyncedTiming = (timing: UnsyncedTiming) => {
+        setUnsyncedTiming(timing);
+        invoke("set_unsynced_timing", { timing }).catch(console.error);
+    };
+
+    const changeSourcePolicy = async (policy: ArbitrationPolicy) => {
+        setSourcePolicy(policy);
+        try {
+            await invoke("set_source_policy", { policy });
+        } catch (e) {
+            console.error(e);
+        }
+    };
+
+    const changePolicyMode = (mode: ArbitrationPolicy["mode"]) => {
+        if (mode === "fixedPriority") {
+            changeSourcePolicy({ mode, order: sources.map((s) => s.id) });
+        } else if (mode === "manual") {
+            const active = sources.find((s) => s.active) ?? sources[0];
+            changeSourcePolicy({ mode, source: active?.id ?? "default" });
+        } else {
+            changeSourcePolicy({ mode });
+        }
+    };
+
+    const toggleTokenRequired = async (required: boolean) => {
+        try {
+            await invoke("set_token_required", { required });
+            setAuthInfo((prev) => (prev ? { ...prev, required } : prev));
+        } catch (e) {
+            console.error(e);
+        }
+    };
+
+    const regenerateToken = async () => {
+        if (!confirm(t.regenerateConfirm)) return;
+        try {
+            const token = await invoke<string>("regenerate_auth_token");
+            setAuthInfo((prev) => (prev ? { ...prev, token } : prev));
+        } catch (e) {
+            console.error(e);
+        }
+    };
+
     const update = <K extends keyof OverlaySettings>(
         key: K,
         value: OverlaySettings[K]
@@ -648,6 +984,27 @@ export default function SettingsPanelNew({
                                 <SettingItem label={t.translationLyrics}>
                                     <Toggle checked={settings.showTranslation} onChange={(v) => update("showTranslation", v)} />
                                 </SettingItem>
+                                {settings.showTranslation && (
+                                    <SettingItem label={t.translationLanguages} description={t.translationLanguagesDesc} column>
+                                        <input
+                                            key={translationLanguages.join(",")}
+                                            className="token-field"
+                                            defaultValue={translationLanguages.join(", ")}
+                                            onBlur={(e) => changeTranslationLanguages(e.target.value)}
+                                        />
+                                    </SettingItem>
+                                )}
+                                <SettingItem label={t.unsyncedTiming} description={t.unsyncedTimingDesc}>
+                                    <select
+                                        className="settings-select"
+                                        value={unsyncedTiming}
+                                        onChange={(e) => changeUnsyncedTiming(e.target.value as UnsyncedTiming)}
+                                    >
+                                        <option value="weighted">{t.unsyncedWeighted}</option>
+                                        <option value="proportional">{t.unsyncedProportional}</option>
+                                        <option value="off">{t.unsyncedOff}</option>
+                                    </select>
+                                </SettingItem>
                                 <SettingItem label={t.trackInfo}>
                                     <Toggle checked={settings.showTrackInfo} onChange={(v) => update("showTrackInfo", v)} />
                                 </SettingItem>
@@ -969,6 +1326,193 @@ export default function SettingsPanelNew({
                                 )}
                             </SettingSection>
 
+                            <SettingSection title={t.connectionSection} delay={75}>
+                                <SettingItem
+                                    label={t.serverAddress}
+                                    description={serverStatus?.state === "error" ? serverStatus.message : undefined}
+                                >
+                                    <span className="setting-value">
+                                        {serverStatus?.state === "listening"
+                                            ? [serverStatus.url, serverStatus.ipcPath].filter(Boolean).join(" · ")
+                                            : serverStatus?.state === "starting"
+                                                ? t.serverStarting
+                                                : serverStatus?.state === "error"
+                                                    ? t.serverError
+                                                    : t.serverNotRunning}
+                                    </span>
+                                </SettingItem>
+                                <div className="setting-item">
+                                    <button
+                                        className="action-btn"
+                                        onClick={() => invoke("restart_server").catch(console.error)}
+                                    >
+                                        {t.restartServer}
+                                    </button>
+                                </div>
+                                <SettingItem label={t.sourcePolicy}>
+                                    <select
+                                        className="settings-select"
+                                        value={sourcePolicy.mode}
+                                        onChange={(e) => changePolicyMode(e.target.value as ArbitrationPolicy["mode"])}
+                                    >
+                                        <option value="mostRecentlyPlaying">{t.policyMostRecent}</option>
+                                        <option value="fixedPriority">{t.policyPriority}</option>
+                                        <option value="manual">{t.policyManual}</option>
+                                    </select>
+                                </SettingItem>
+                                {sourcePolicy.mode === "fixedPriority" && (
+                                    <SettingItem label={t.priorityOrder} column>
+                                        <input
+                                            className="token-field"
+                                            defaultValue={sourcePolicy.order.join(", ")}
+                                            onBlur={(e) =>
+                                                changeSourcePolicy({
+                                                    mode: "fixedPriority",
+                                                    order: e.target.value
+                                                        .split(",")
+                                                        .map((id) => id.trim())
+                                                        .filter((id) => id.length > 0),
+                                                })
+                                            }
+                                        />
+                                    </SettingItem>
+                                )}
+                                {sourcePolicy.mode === "manual" && (
+                                    <SettingItem label={t.manualSource}>
+                                        {sources.length > 0 ? (
+                                            <select
+                                                className="settings-select"
+                                                value={sourcePolicy.source}
+                                                onChange={(e) => changeSourcePolicy({ mode: "manual", source: e.target.value })}
+                                            >
+                                                {sources.map((s) => (
+                                                    <option key={s.id} value={s.id}>
+                                                        {s.track ? `${s.id} (${s.track})` : s.id}
+                                                    </option>
+                                                ))}
+                                            </select>
+                                        ) : (
+                                            <span className="setting-value">{t.noSources}</span>
+                                        )}
+                                    </SettingItem>
+                                )}
+                                <SettingItem
+                                    label={t.rejectedOrigins}
+                                    description={lastRejected?.origin}
+                                >
+                                    <span className="setting-value">
+                                        {lastRejected ? lastRejected.totalRejected : t.rejectedOriginsNone}
+                                    </span>
+                                </SettingItem>
+                                {latency && (
+                                    <SettingItem
+                                        label={t.positionOffset}
+                                        description={latency.delayMs !== null
+                                            ? t.measuredDelay.replace("{delay}", String(Math.round(latency.delayMs)))
+                                            : t.measuredDelayNone}
+                                        column
+                                    >
+                                        <Slider
+                                            value={latency.offsetMs}
+                                            onChange={changePositionOffset}
+                                            min={-2000} max={2000} step={50} suffix={t.ms}
+                                        />
+                                    </SettingItem>
+                                )}
+                                <SettingItem
+                                    label={t.trackOffset}
+                                    description={trackOffset?.track ?? t.trackOffsetNone}
+                                >
+                                    <span className="setting-value">
+                                        {trackOffset ? `${trackOffset.offsetMs > 0 ? "+" : ""}${trackOffset.offsetMs}${t.ms}` : "-"}
+                                    </span>
+                                </SettingItem>
+                                {trackOffset?.track && (
+                                    <div className="setting-item">
+                                        <button className="action-btn" onClick={() => nudgeTrackOffset(-100)}>
+                                            {t.later} -100{t.ms}
+                                        </button>
+                                        <button className="action-btn" onClick={() => nudgeTrackOffset(100)}>
+                                            {t.earlier} +100{t.ms}
+                                        </button>
+                                        <button className="action-btn" onClick={resetTrackOffset}>
+                                            {t.resetOffset}
+                                        </button>
+                                    </div>
+                                )}
+                                {progressStats && (
+                                    <SettingItem
+                                        label={t.progressEvents}
+                                        description={t.progressEventsDesc
+                                            .replace("{received}", String(progressStats.received))
+                                            .replace("{immediate}", String(progressStats.immediate))
+                                            .replace("{coalesced}", String(progressStats.coalesced))}
+                                    >
+                                        <span className="setting-value">{progressStats.emitted}</span>
+                                    </SettingItem>
+                                )}
+                                <SettingItem label={t.requireToken} description={t.requireTokenDesc}>
+                                    <Toggle
+                                        checked={authInfo?.required ?? false}
+                                        onChange={toggleTokenRequired}
+                                    />
+                                </SettingItem>
+                                <SettingItem label={t.authToken} column>
+                                    <input
+                                        className="token-field"
+                                        value={authInfo?.token ?? ""}
+                                        readOnly
+                                        onFocus={(e) => e.target.select()}
+                                    />
+                                </SettingItem>
+                                <div className="setting-item">
+                                    <button className="action-btn" onClick={regenerateToken}>
+                                        {t.regenerateToken}
+                                    </button>
+                                </div>
+                            </SettingSection>
+
+                            <SettingSection title={t.profilesSection} delay={90}>
+                                <SettingItem label={t.profilesSection}>
+                                    {Object.keys(profiles).length > 0 ? (
+                                        <select
+                                            className="settings-select"
+                                            value={selectedProfile}
+                                            onChange={(e) => setSelectedProfile(e.target.value)}
+                                        >
+                                            <option value="" disabled>-</option>
+                                            {Object.keys(profiles).map((name) => (
+                                                <option key={name} value={name}>{name}</option>
+                                            ))}
+                                        </select>
+                                    ) : (
+                                        <span className="setting-value">{t.noProfiles}</span>
+                                    )}
+                                </SettingItem>
+                                {selectedProfile && (
+                                    <div className="setting-item">
+                                        <button className="action-btn" onClick={applyProfile}>
+                                            {t.applyProfile}
+                                        </button>
+                                        <button className="action-btn danger" onClick={deleteProfile}>
+                                            {t.deleteProfile}
+                                        </button>
+                                    </div>
+                                )}
+                                <SettingItem label={t.profileName} column>
+                                    <input
+                                        className="token-field"
+                                        value={profileName}
+                                        onChange={(e) => setProfileName(e.target.value)}
+                                    />
+                                </SettingItem>
+                                <div className="setting-item">
+                                    <button className="action-btn" onClick={saveCurrentProfile} disabled={!profileName.trim()}>
+                                        {t.saveProfile}
+                                    </button>
+                                </div>
+                            </SettingSection>
+
                             <SettingSection title={t.advancedSection} delay={100}>
                                 <SettingItem label={t.customCSS} column>
                                     <textarea
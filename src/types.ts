@@ -1,4 +1,5 @@
 export interface TrackInfo {
+    id?: string;
     title: string;
     artist: string;
     album: string;
@@ -6,21 +7,44 @@ export interface TrackInfo {
     duration: number;
 }
 
+// 단어/음절 단위 타이밍 (가라오케 하이라이트)
+export interface LyricSegment {
+    text: string;
+    startTime: number;
+    endTime: number;
+    singer?: string | null; // 줄의 가수와 다를 때만
+}
+
 export interface LyricLine {
     startTime: number;
     endTime?: number;
     text: string;
     pronText?: string;
     transText?: string;
+    translations?: Record<string, string>; // 언어 태그별 번역 (예: "ko", "en")
+    segments?: LyricSegment[];
+    singer?: string | null; // 듀엣 등에서 이 줄을 부르는 가수/파트 (예: "v1", "v2")
+    backgroundText?: string | null; // 코러스/백그라운드 보컬
+    kind?: "lyric" | "intro" | "interlude"; // 백엔드가 넣은 간주 표시 줄 (text는 비어 있음)
     translation?: string; // For backward compatibility if needed, though lib.rs dicts strict shape, but frontend code might use it?
 }
 
+// 새로 선택된 소스가 아직 보내지 않은 데이터 (이전 소스 것을 지움)
+export interface NowPlayingCleared {
+    lyrics: boolean;
+    progress: boolean;
+}
+
 export interface LyricsData {
     track: TrackInfo;
     lyrics: LyricLine[];
     isSynced: boolean;
+    source?: string;
+    estimatedTiming?: UnsyncedTiming; // 싱크 없는 가사에 백엔드가 추정한 타이밍
 }
 
+export type UnsyncedTiming = "off" | "weighted" | "proportional";
+
 export interface NextTrackInfo {
     title: string;
     artist: string;
@@ -33,6 +57,8 @@ export interface ProgressData {
     duration?: number;
     remaining?: number;
     nextTrack?: NextTrackInfo | null;
+    source?: string;
+    sentAt?: number; // 위치를 측정한 시각 (epoch ms), 지연 보정에 사용
 }
 
 export interface LyricsEvent {
@@ -42,3 +68,83 @@ export interface LyricsEvent {
 export interface ProgressEvent {
     progressData: ProgressData;
 }
+
+// 늦게 도착한 번역/발음 (인덱스로 해석된 줄만 포함)
+export interface PatchedLine {
+    index: number;
+    transText?: string | null;
+    pronText?: string | null;
+    translations?: Record<string, string>; // 패치 후 이 줄의 (선택된) 번역 전체
+}
+
+export interface LyricsPatchEvent {
+    trackId: string;
+    lines: PatchedLine[];
+}
+
+// 오버레이 → 플레이어 명령 (enqueue_player_command)
+export type PlayerCommand =
+    | { type: "seek"; positionMs: number }
+    | { type: "playPause" }
+    | { type: "next" }
+    | { type: "previous" }
+    | { type: "resendLyrics" };
+
+// 백엔드 재생 시계가 줄 경계에서 보내는 이벤트
+export interface LineChangeEvent {
+    previous: number | null;
+    current: number | null;
+    next: number | null;
+    position: number;
+}
+
+// 연속된 재생 위치 업데이트를 비교해 백엔드가 보내는 이벤트
+export interface SeekEvent {
+    from: number;
+    to: number;
+    expected: number;
+}
+
+export interface StallEvent {
+    stalled: boolean;
+    positionBefore: number;
+    positionAfter: number;
+    stalledMs: number;
+}
+
+export interface TrackSummary {
+    id: string;
+    title: string;
+    artist: string;
+}
+
+export interface TrackChangeEvent {
+    from: TrackSummary | null;
+    to: TrackSummary;
+}
+
+export interface ClockSnapshot {
+    position: number;
+    isPlaying: boolean;
+    current: number | null;
+    next: number | null;
+}
+
+export interface StateSnapshot {
+    lyricsData: LyricsData | null;
+    progressData: ProgressData | null;
+    source: string | null;
+}
+
+export interface SourceInfo {
+    id: string;
+    isPlaying: boolean;
+    idleMs: number;
+    track: string | null;
+    active: boolean;
+}
+
+export type ArbitrationPolicy =
+    | { mode: "mostRecentlyPlaying" }
+    | { mode: "fixedPriority"; order: string[] }
+    | { mode: "manual"; source: string };